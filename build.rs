@@ -5,7 +5,7 @@
 #[macro_use]
 extern crate build_cfg;
 
-use std::{env, io, path::{Path, PathBuf}};
+use std::{env, fs, io, path::{Path, PathBuf}};
 
 /// The error type returned by [`main`].
 #[derive(Debug)]
@@ -16,12 +16,59 @@ pub enum Error {
     /// Failed to build *libui*.
     #[cfg(feature = "build")]
     BuildLibui(build::Error),
-    /// Failed to include Windows resources.
-    IncludeWinres(io::Error),
     /// Failed to generate bindings to *libui*.
     GenBindings(bindings::Error),
+    /// Failed to write `build-summary.json`.
+    WriteSummary(io::Error),
+    /// Failed to write `enabled-features.rs`.
+    WriteFeatures(io::Error),
+    /// A vendored dependency is missing because its Git submodule wasn't checked out.
+    SubmoduleNotInitialized {
+        submodule: &'static str,
+        resolution: &'static str,
+    },
+    /// `prebuilt-bindings` is enabled, but the `bindings` directory lacks bindings for the
+    /// target platform.
+    MissingPrebuiltBindings {
+        resolution: &'static str,
+    },
+    /// Two enabled features contradict each other.
+    ConflictingFeatures {
+        features: [&'static str; 2],
+        resolution: &'static str,
+    },
 }
 
+/// Pairs of features that cannot be meaningfully enabled together, each with advice on resolving
+/// the conflict.
+const CONFLICTING_FEATURES: &[([&str; 2], &str)] = &[
+    (
+        ["prebuilt-bindings", "strict-bindings"],
+        "`strict-bindings` checks the bindings that bindgen generates, but `prebuilt-bindings` \
+        skips generating them; disable one of the two",
+    ),
+    (
+        ["build", "gtk4"],
+        "libui-ng can't yet be built against GTK 4, so `gtk4` requires a system libui-ng; \
+        disable `build` (e.g., with `default-features = false`) or `gtk4`",
+    ),
+    (
+        ["build-with-msvc", "build-with-xcode"],
+        "only one build backend may be selected; disable one of the two, or enable \
+        `auto-backend` instead to have one detected",
+    ),
+    (
+        ["universal-macos", "build-with-msvc"],
+        "`universal-macos` requires building libui-ng with Ninja; enable `build-with-ninja` \
+        instead of `build-with-msvc`",
+    ),
+    (
+        ["universal-macos", "build-with-xcode"],
+        "`universal-macos` requires building libui-ng with Ninja; enable `build-with-ninja` \
+        instead of `build-with-xcode`",
+    ),
+];
+
 #[build_cfg_main]
 fn main() -> Result<(), Error> {
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
@@ -29,18 +76,49 @@ fn main() -> Result<(), Error> {
     let meson_dir = out_dir.join("meson");
     let ninja_dir = out_dir.join("ninja");
 
+    validate_features()?;
+
+    // `lib.rs` compiles out everything that uses the bindings when only stubs of them exist.
+    println!("cargo:rustc-check-cfg=cfg(libui_ng_sys_stub_bindings)");
+    // Editor tooling on machines without Clang can still make sense of the crate if we write empty
+    // stubs in place of the real bindings. Nothing is built or linked in that case, so this comes
+    // before the submodules are checked: they may well be uninitialized, too.
+    if bindings::writes_stubs() {
+        bindings::write_stubs(&out_dir).map_err(Error::GenBindings)?;
+        write_features(&out_dir).map_err(Error::WriteFeatures)?;
+        println!("cargo:rustc-cfg=libui_ng_sys_stub_bindings");
+        println!("cargo:rerun-if-changed=build.rs");
+
+        return Ok(());
+    }
+
     // Cargo will prevent this crate from being published if the build script modifies files outside
     // `$OUT_DIR` during its operation. To work around this for the purpose of building *libui*, we
     // copy all non-Rust build dependencies to `$OUT_DIR`.
+    check_submodule("libui-ng", "ui.h")?;
     dep::sync("libui-ng", &libui_dir).map_err(Error::SyncDep)?;
 
+    let mut link = link::LinkSpec::default();
+    let mut summary = summary::Summary {
+        link_kind: link_kind(),
+        libui_commit: bindings::libui_commit(),
+        ..Default::default()
+    };
+
     #[cfg(feature = "build")]
     if env::var("DOCS_RS").is_err() {
         let backend = build::Backend::default();
+        summary.backend = Some(backend.as_str());
 
-        dep::sync("meson", &meson_dir).map_err(Error::SyncDep)?;
-        // Ninja only needs to be synced if it's selected as a build backend.
-        if let build::Backend::Ninja = backend {
+        // Meson needn't be synced if it's already installed.
+        if backend.system_meson().is_none() {
+            check_submodule("meson", "meson.py")?;
+            dep::sync("meson", &meson_dir).map_err(Error::SyncDep)?;
+        }
+        // Ninja only needs to be synced if it's selected as a build backend and isn't already
+        // installed.
+        if matches!(backend, build::Backend::Ninja) && build::Backend::system_ninja().is_none() {
+            check_submodule("ninja", "configure.py")?;
             // When downloading crates from *crates.io*, file execute permissions are *not*
             // respected. This is a problem for Ninja, which attempts to execute a file named
             // *inline.sh*. For this reason, we manually mark it as executable.
@@ -53,26 +131,66 @@ fn main() -> Result<(), Error> {
         backend.build_libui(&libui_dir, &meson_dir, &ninja_dir).map_err(Error::BuildLibui)?;
 
         // Tell Cargo where to find the copy of *libui* that we just built.
-        println!(
-            "cargo:rustc-link-search={}",
-            libui_dir.join("build/meson-out/").display(),
-        );
-
-        // Because we are building *libui* from scratch and placing it in `$OUT_DIR`, it makes sense
-        // to link statically. Consequently, as static libraries *do not* contain information on the
-        // shared objects that must be imported, we must tell Cargo (and, by extension, the dynamic
-        // linker) which shared objects we need.
-        import_dylibs();
+        link.search(libui_dir.join("build/meson-out/"));
+
+        if cfg!(feature = "build-both") {
+            let shared_dir = out_dir.join("shared");
+            build::Backend::export_shared_libui(&libui_dir, &shared_dir)
+                .map_err(Error::BuildLibui)?;
+            // Dependents may read this as `$DEP_UI_SHARED_DIR`.
+            println!("cargo:shared_dir={}", shared_dir.display());
+        }
 
-        if build_cfg!(target_os = "windows") && cfg!(feature = "include-win-manifest") {
-            include_winres().map_err(Error::IncludeWinres)?;
+        if build_cfg!(target_os = "windows") && includes_win_manifest() {
+            // The manifest is only polish, so failing to compile it (e.g., because `rc.exe` is
+            // missing) shouldn't fail the build.
+            if let Err(e) = include_winres() {
+                println!(
+                    "cargo:warning=failed to embed the Windows manifest; continuing without it: {}",
+                    e,
+                );
+            }
         }
     }
 
-    // Instruct Cargo to link to *libui*.
-    println!("cargo:rustc-link-lib={}=ui", link_kind());
+    // Instruct Cargo to link to *libui*, unless the generated bindings carry `#[link]` attributes
+    // that do so themselves.
+    if !cfg!(feature = "link-attrs") || cfg!(feature = "prebuilt-bindings") {
+        link.lib(link_kind(), "ui");
+    }
 
-    bindings::generate(&libui_dir, &out_dir).map_err(Error::GenBindings)?;
+    // Static libraries *do not* contain information on the shared objects that must be imported,
+    // so we must tell Cargo (and, by extension, the dynamic linker) which shared objects we need.
+    // We do so even when linking a system *libui*, which may be static too. *docs.rs* links
+    // nothing and may lack the platform's GUI toolkit.
+    if env::var("DOCS_RS").is_err() {
+        summary.system_libs.extend(import_dylibs(&mut link));
+    }
+
+    if cfg!(feature = "prebuilt-bindings") {
+        // `lib.rs` includes the bindings bundled with the crate instead. Without them, it would
+        // fail to compile with a far less helpful error.
+        if !bindings::has_committed() {
+            return Err(Error::MissingPrebuiltBindings {
+                resolution: "build once without `prebuilt-bindings` and with \
+                    `LIBUI_NG_SYS_EMIT_BINDINGS_TO` set to the `bindings` directory of \
+                    libui-ng-sys to populate it",
+            });
+        }
+    } else if env::var("DOCS_RS").is_ok() && bindings::has_committed() {
+        // *docs.rs* may lack Clang, so we document the committed bindings instead, if there are
+        // any for this platform.
+        bindings::copy_committed(&out_dir).map_err(Error::GenBindings)?;
+    } else {
+        log::step("generate bindings", || {
+            bindings::generate(&libui_dir, &out_dir, &mut summary)
+        })
+        .map_err(Error::GenBindings)?;
+    }
+
+    link.emit();
+    summary.write(&out_dir).map_err(Error::WriteSummary)?;
+    write_features(&out_dir).map_err(Error::WriteFeatures)?;
 
     // Recompile *libui-ng-sys* whenever this build script is modified.
     println!("cargo:rerun-if-changed=build.rs");
@@ -80,6 +198,70 @@ fn main() -> Result<(), Error> {
     Ok(())
 }
 
+/// Fails if the vendored dependency `name` lacks `file`, which every checkout of it contains.
+///
+/// Git leaves submodules empty until they are initialized, in which case [`dep::sync`] would
+/// happily copy nothing and the build would fail much later with a far less helpful error.
+fn check_submodule(name: &'static str, file: &str) -> Result<(), Error> {
+    let path = Path::new("dep").join(name).join(file);
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(());
+    }
+
+    Err(Error::SubmoduleNotInitialized {
+        submodule: name,
+        resolution: "run `git submodule update --init` in the libui-ng-sys repository",
+    })
+}
+
+/// Fails if both features of any pair in [`CONFLICTING_FEATURES`] are enabled.
+///
+/// Otherwise, one of the two would silently win, which is easy to miss.
+fn validate_features() -> Result<(), Error> {
+    let is_enabled = |name: &str| {
+        env::var_os(format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"))).is_some()
+    };
+
+    for &(features, resolution) in CONFLICTING_FEATURES {
+        if features.iter().all(|name| is_enabled(name)) {
+            return Err(Error::ConflictingFeatures { features, resolution });
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the names of the enabled Cargo features to `$OUT_DIR/enabled-features.rs`, which
+/// `lib.rs` includes as `ENABLED_FEATURES`.
+///
+/// This is written even when prebuilt bindings are used, as those would otherwise report the
+/// features of whoever generated them.
+fn write_features(out_dir: &Path) -> io::Result<()> {
+    // Cargo uppercases feature names and replaces dashes with underscores; every feature of this
+    // crate is lowercase and uses dashes, so this is reversible.
+    let mut features = env::vars()
+        .filter_map(|(key, _)| {
+            let name = key.strip_prefix("CARGO_FEATURE_")?;
+            Some(name.to_lowercase().replace('_', "-"))
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+
+    let features = features
+        .iter()
+        .map(|name| format!("    {:?},\n", name))
+        .collect::<String>();
+    let contents = format!(
+        "\
+            /// The Cargo features with which *libui-ng-sys* was compiled, sorted by name.\n\
+            pub const ENABLED_FEATURES: &[&str] = &[\n{}];\n\
+        ",
+        features,
+    );
+
+    fs::write(out_dir.join("enabled-features.rs"), contents)
+}
+
 #[cfg(all(feature = "build", unix))]
 fn mark_executable(path: impl AsRef<Path>) -> Result<(), Error> {
     use std::{fs, os::unix::fs::PermissionsExt as _};
@@ -87,19 +269,33 @@ fn mark_executable(path: impl AsRef<Path>) -> Result<(), Error> {
     fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(Error::SetPermissions)
 }
 
-#[cfg(feature = "build")]
-fn import_dylibs() {
+/// Adds the shared objects that *libui* imports to `link`, returning their names.
+fn import_dylibs(link: &mut link::LinkSpec) -> Vec<String> {
+    let mut names = Vec::new();
+
     macro_rules! dyn_link {
         ($($name:tt)*) => {
             $(
-                println!("cargo:rustc-link-lib=dylib={}", stringify!($name));
+                names.push(stringify!($name).to_string());
             )*
         };
     }
 
     if build_cfg!(target_os = "linux") {
-        // While unintuitive, we don't actually need to specify any shared objects here---the
-        // `pkg_config` crate will do that automatically in [`bindings::ClangArgs::new_linux`].
+        // Fully static binaries need GTK's transitive static libraries rather than just its shared
+        // objects.
+        let statik = cfg!(feature = "fully-static");
+        if statik {
+            println!(
+                "cargo:warning=linking GTK statically is fragile; GTK may fail to load its \
+                modules and themes at runtime"
+            );
+        }
+
+        let gtk = gtk::probe();
+        link.pkg_config(&gtk, statik);
+
+        return gtk.libs;
     } else if build_cfg!(target_os = "windows") {
         // See `dep/libui-ng/windows/meson.build`.
         dyn_link! {
@@ -118,7 +314,38 @@ fn import_dylibs() {
             uxtheme
             windowscodecs
         };
+
+        // Newer versions of *libui* may depend on system libraries missing from the list above;
+        // users may supply these themselves.
+        names.extend(config::list("LIBUI_NG_SYS_WIN_LIBS"));
+    }
+
+    for name in &names {
+        link.lib("dylib", name);
+    }
+
+    names
+}
+
+/// Determines if the manifest enabling Common Controls v6, which *libui* requires, should be
+/// embedded in the final binary.
+///
+/// `no-win-manifest` overrides `include-win-manifest`, which is enabled by default, for
+/// applications that supply their own manifest; embedding two manifests fails to link.
+#[cfg(feature = "build")]
+fn includes_win_manifest() -> bool {
+    if cfg!(feature = "no-win-manifest") {
+        if cfg!(feature = "include-win-manifest") {
+            println!(
+                "cargo:warning=`no-win-manifest` is enabled, so `include-win-manifest` has no \
+                effect"
+            );
+        }
+
+        return false;
     }
+
+    cfg!(feature = "include-win-manifest")
 }
 
 fn include_winres() -> io::Result<()> {
@@ -135,12 +362,289 @@ fn link_kind() -> &'static str {
     }
 }
 
+#[path = "build/depfile.rs"]
+mod depfile;
+#[path = "build/link.rs"]
+mod link;
+#[cfg(feature = "build")]
+#[path = "build/version.rs"]
+mod version;
+
+mod config {
+    use std::{env, path::PathBuf};
+
+    /// Returns the value of the environment variable `name`, if set, and tells Cargo to rerun the
+    /// build script whenever it changes.
+    pub fn var(name: &str) -> Option<String> {
+        println!("cargo:rerun-if-env-changed={}", name);
+
+        env::var(name).ok()
+    }
+
+    /// Returns `Some(true)` if the environment variable `name` is set to a truthy value,
+    /// `Some(false)` if it is set to a falsy value, and `None` if it is unset.
+    pub fn flag(name: &str) -> Option<bool> {
+        var(name).map(|value| !matches!(value.as_str(), "" | "0" | "false" | "no" | "off"))
+    }
+
+    /// Returns the semicolon-separated, non-empty entries of the environment variable `name`.
+    pub fn list(name: &str) -> Vec<String> {
+        var(name)
+            .map(|value| {
+                value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|entry| !entry.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Searches `$PATH` for an executable named `name`.
+    pub fn find_program(name: &str) -> Option<PathBuf> {
+        let filename = PathBuf::from(name).with_extension(env::consts::EXE_EXTENSION);
+
+        env::split_paths(&env::var_os("PATH")?)
+            .map(|dir| dir.join(&filename))
+            .find(|path| path.is_file())
+    }
+}
+
+mod macos {
+    use crate::config;
+
+    /// Returns the macOS version requested with `$MACOSX_DEPLOYMENT_TARGET` when targeting macOS.
+    ///
+    /// *rustc* reads this variable too, so honoring it keeps the compiled *libui* and its bindings
+    /// in agreement with the final binary.
+    pub fn deployment_target() -> Option<String> {
+        if build_cfg!(target_os = "macos") {
+            config::var("MACOSX_DEPLOYMENT_TARGET").filter(|version| !version.is_empty())
+        } else {
+            None
+        }
+    }
+}
+
+mod gtk {
+    use std::{env, iter, path::PathBuf, sync::OnceLock};
+
+    use crate::config;
+
+    /// The name of the pkg-config package for GTK.
+    #[cfg(not(feature = "gtk4"))]
+    pub static PACKAGE: &str = "gtk+-3.0";
+    #[cfg(feature = "gtk4")]
+    pub static PACKAGE: &str = "gtk4";
+
+    /// The minimum version of GTK supported by *libui*.
+    #[cfg(not(feature = "gtk4"))]
+    pub static MIN_VERSION: &str = "3.10.0";
+    #[cfg(feature = "gtk4")]
+    pub static MIN_VERSION: &str = "4.0.0";
+
+    /// Returns the directory containing the GTK pkg-config file selected with
+    /// `$LIBUI_NG_SYS_GTK_PKG`, if any.
+    ///
+    /// The variable may name either the `.pc` file itself or the directory containing it. Both
+    /// Meson and bindgen search this directory first so that the compiled *libui* and its bindings
+    /// agree on GTK.
+    pub fn pkg_config_dir() -> Option<PathBuf> {
+        let path = PathBuf::from(config::var("LIBUI_NG_SYS_GTK_PKG")?);
+        if path.extension().is_some_and(|ext| ext == "pc") {
+            path.parent().map(PathBuf::from)
+        } else {
+            Some(path)
+        }
+    }
+
+    /// Locates GTK with pkg-config, probing only once.
+    ///
+    /// Cargo is not told to link against GTK; that is left to [`crate::link::LinkSpec`]. When
+    /// cross-compiling, the `pkg_config` crate only probes if `$PKG_CONFIG_SYSROOT_DIR`,
+    /// `$PKG_CONFIG`, or `$PKG_CONFIG_ALLOW_CROSS` is set, including for the target alone.
+    pub fn probe() -> pkg_config::Library {
+        static GTK: OnceLock<pkg_config::Library> = OnceLock::new();
+
+        GTK.get_or_init(|| {
+            if let Some(dir) = pkg_config_dir() {
+                // pkg-config is only told of search paths through its environment. The
+                // target-specific variable is the first that the `pkg_config` crate consults.
+                let paths = targeted_var("PKG_CONFIG_PATH").unwrap_or_default();
+                let paths = iter::once(dir).chain(env::split_paths(&paths));
+                env::set_var(
+                    format!("PKG_CONFIG_PATH_{}", env::var("TARGET").unwrap()),
+                    env::join_paths(paths).unwrap(),
+                );
+            }
+
+            pkg_config::Config::new()
+                .atleast_version(MIN_VERSION)
+                .statik(cfg!(feature = "fully-static"))
+                .cargo_metadata(false)
+                .env_metadata(true)
+                .print_system_cflags(true)
+                .print_system_libs(true)
+                .probe(PACKAGE)
+                .unwrap()
+        })
+        .clone()
+    }
+
+    /// Returns the value of the pkg-config variable `name` as the `pkg_config` crate reads it,
+    /// preferring variants specific to the target triple or to whether we are cross-compiling.
+    fn targeted_var(name: &str) -> Option<String> {
+        let (target, host) = (env::var("TARGET").unwrap(), env::var("HOST").unwrap());
+        let kind = if host == target { "HOST" } else { "TARGET" };
+
+        config::var(&format!("{}_{}", name, target))
+            .or_else(|| config::var(&format!("{}_{}", name, target.replace('-', "_"))))
+            .or_else(|| config::var(&format!("{}_{}", kind, name)))
+            .or_else(|| config::var(name))
+    }
+}
+
+impl link::LinkSpec {
+    /// Adds the libraries, search paths, and linker arguments reported by pkg-config.
+    ///
+    /// If `statik` is `true`, each library of which a static archive is found in the reported
+    /// search paths is linked statically.
+    pub fn pkg_config(&mut self, lib: &pkg_config::Library, statik: bool) {
+        for dir in lib.link_paths.iter().chain(&lib.framework_paths) {
+            self.search(dir);
+        }
+        for name in &lib.libs {
+            let has_archive = || {
+                lib.link_paths.iter().any(|dir| dir.join(format!("lib{}.a", name)).is_file())
+            };
+            let kind = if statik && has_archive() { "static" } else { "dylib" };
+            self.lib(kind, name);
+        }
+        for name in &lib.frameworks {
+            self.lib("framework", name);
+        }
+        for args in &lib.ld_args {
+            self.arg(format!("-Wl,{}", args.join(",")));
+        }
+    }
+}
+
+mod log {
+    use std::{sync::OnceLock, time::{Instant, SystemTime, UNIX_EPOCH}};
+
+    use crate::config;
+
+    /// Runs `f` as the build step named `name`.
+    ///
+    /// If `$LIBUI_NG_SYS_VERBOSE` is set, the start and duration of the step are reported as Cargo
+    /// warnings so that users can see where build time goes.
+    pub fn step<T>(name: &str, f: impl FnOnce() -> T) -> T {
+        static IS_VERBOSE: OnceLock<bool> = OnceLock::new();
+
+        if !*IS_VERBOSE.get_or_init(|| config::flag("LIBUI_NG_SYS_VERBOSE") == Some(true)) {
+            return f();
+        }
+
+        println!("cargo:warning=[{}] {}: started", timestamp(), name);
+        let start = Instant::now();
+        let result = f();
+        println!(
+            "cargo:warning=[{}] {}: finished in {:.2?}",
+            timestamp(),
+            name,
+            start.elapsed(),
+        );
+
+        result
+    }
+
+    /// Returns the current time as seconds since the Unix epoch.
+    fn timestamp() -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        format!("{}.{:03}", now.as_secs(), now.subsec_millis())
+    }
+}
+
+mod summary {
+    use std::{fs, io, path::Path};
+
+    /// A record of what the build script did, written to `$OUT_DIR/build-summary.json` for the
+    /// benefit of packaging tools.
+    #[derive(Default)]
+    pub struct Summary {
+        /// The Meson backend used to build *libui*, if it was built.
+        pub backend: Option<&'static str>,
+        /// How *libui* is linked: `"static"` or `"dylib"`.
+        pub link_kind: &'static str,
+        /// The Git commit of *libui-ng*, if known.
+        pub libui_commit: Option<String>,
+        /// The system libraries that *libui* is linked against.
+        pub system_libs: Vec<String>,
+        /// The include paths passed to Clang when generating bindings.
+        pub include_paths: Vec<String>,
+    }
+
+    impl Summary {
+        pub fn write(&self, out_dir: &Path) -> io::Result<()> {
+            let contents = format!(
+                "{{\n  \"backend\": {},\n  \"link_kind\": {},\n  \"libui_commit\": {},\n  \
+                \"system_libs\": {},\n  \"include_paths\": {}\n}}\n",
+                optional_string(self.backend),
+                string(self.link_kind),
+                optional_string(self.libui_commit.as_deref()),
+                array(&self.system_libs),
+                array(&self.include_paths),
+            );
+
+            fs::write(out_dir.join("build-summary.json"), contents)
+        }
+    }
+
+    fn array(items: &[String]) -> String {
+        let items: Vec<String> = items.iter().map(|it| string(it)).collect();
+
+        format!("[{}]", items.join(", "))
+    }
+
+    fn optional_string(value: Option<&str>) -> String {
+        value.map(string).unwrap_or_else(|| String::from("null"))
+    }
+
+    /// Formats `value` as a JSON string literal.
+    fn string(value: &str) -> String {
+        let mut out = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+
+        out
+    }
+}
+
 mod dep {
     use std::path::Path;
 
+    use crate::log;
+
     pub fn sync(name: &str, to: &Path) -> Result<(), anyhow::Error> {
+        log::step(&format!("sync {}", name), || copy(&Path::new("dep").join(name), to))
+    }
+
+    #[cfg(feature = "rusync")]
+    fn copy(from: &Path, to: &Path) -> Result<(), anyhow::Error> {
         rusync::Syncer::new(
-            &Path::new("dep").join(name),
+            from,
             to,
             rusync::SyncOptions {
                 preserve_permissions: true,
@@ -151,23 +655,84 @@ mod dep {
         .map(|_| ())
     }
 
+    #[cfg(not(feature = "rusync"))]
+    fn copy(from: &Path, to: &Path) -> Result<(), anyhow::Error> {
+        copy_dir_recursive(from, to).map_err(anyhow::Error::from)
+    }
+
+    /// Recursively copies the directory `from` to `to`.
+    ///
+    /// Like *rusync*, files whose destination is at least as new and of the same size are skipped.
+    /// Permissions are preserved.
+    #[cfg(not(feature = "rusync"))]
+    fn copy_dir_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+        use std::fs;
+
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            let src = entry.path();
+            let dst = to.join(entry.file_name());
+            let src_meta = fs::metadata(&src)?;
+
+            if src_meta.is_dir() {
+                copy_dir_recursive(&src, &dst)?;
+                continue;
+            }
+
+            let is_up_to_date = fs::metadata(&dst).is_ok_and(|dst_meta| {
+                dst_meta.len() == src_meta.len()
+                    && matches!(
+                        (dst_meta.modified(), src_meta.modified()),
+                        (Ok(dst_time), Ok(src_time)) if dst_time >= src_time,
+                    )
+            });
+            if !is_up_to_date {
+                // `fs::copy` also copies permission bits.
+                fs::copy(&src, &dst)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "rusync")]
     struct FakeProgressInfo;
 
+    #[cfg(feature = "rusync")]
     impl rusync::progress::ProgressInfo for FakeProgressInfo {}
 }
 
+#[cfg(feature = "build")]
 mod build {
-    use std::{env, fs, io, path::{Path, PathBuf}, process};
+    use std::{env, fs, io, iter, path::{Path, PathBuf}, process, sync::OnceLock};
+
+    use crate::{config, gtk, log, macos, version};
 
     /// The error type returned by [`Backend`] functions.
     #[derive(Debug)]
     pub enum Error {
+        /// Failed to remove a previous *libui* build.
+        CleanLibui(io::Error),
         /// Failed to setup *libui*.
         SetupLibui(PythonError),
         /// Failed to build Ninja.
         BuildNinja(PythonError),
         /// Failed to compile *libui*.
         CompileLibui(PythonError),
+        /// Failed to run `nm` on the compiled *libui*.
+        InspectLibui(io::Error),
+        /// The compiled *libui* does not define the given symbols.
+        MissingSymbols(Vec<String>),
+        /// The shared *libui* does not export the given symbols, though the static archive defines
+        /// them.
+        UnexportedSymbols(Vec<String>),
+        /// Failed to copy the shared *libui* out of the build directory.
+        ExportSharedLibui(io::Error),
+        /// Failed to write a Meson cross file.
+        WriteCrossFile(io::Error),
+        /// Failed to combine the libraries built for each architecture with `lipo`.
+        CombineArchs(io::Error),
         /// Failed to rename `libui.a` to `ui.lib`.
         ///
         /// This error *should* only occur when `$CARGO_CFG_TARGET_OS` is `windows`.
@@ -182,6 +747,54 @@ mod build {
         Python { out: process::Output },
     }
 
+    /// An architecture included in universal macOS builds.
+    struct MacosArch {
+        /// The name passed to `-arch`, which is also Meson's `cpu`.
+        name: &'static str,
+        /// Meson's name for the CPU family.
+        cpu_family: &'static str,
+    }
+
+    /// The architectures built by the `universal-macos` feature.
+    static UNIVERSAL_ARCHS: &[MacosArch] = &[
+        MacosArch { name: "arm64", cpu_family: "aarch64" },
+        MacosArch { name: "x86_64", cpu_family: "x86_64" },
+    ];
+
+    /// The minimum version of Meson required by *libui*.
+    static MIN_MESON_VERSION: &str = "0.58.0";
+
+    /// The minimum version of Meson used with the Xcode backend, which must support
+    /// `meson compile --xcode-args`.
+    static MIN_MESON_XCODE_VERSION: &str = "1.0.0";
+
+    /// Returns a `-D<option>=<value>` argument for Meson that adds `args` to the array option
+    /// `option`.
+    ///
+    /// Meson only keeps the last value given for an option, so any value given for `option` in
+    /// `opts` is removed from `opts` and merged into the returned argument, after `args`.
+    fn merge_array_option(opts: &mut Vec<String>, option: &str, args: &[String]) -> String {
+        let prefix = format!("-D{}=", option);
+        let mut value = None;
+        opts.retain(|opt| match opt.strip_prefix(&prefix) {
+            Some(user) => {
+                value = Some(user.trim().to_string());
+                false
+            }
+            None => true,
+        });
+
+        match value.as_deref() {
+            None | Some("") | Some("[]") => format!("{}{}", prefix, args.join(" ")),
+            // Meson also accepts arrays in its own syntax, e.g., `['-O2', '-g']`.
+            Some(user) if user.starts_with('[') => {
+                let args = args.iter().map(|arg| format!("'{}'", arg)).collect::<Vec<_>>();
+                format!("{}[{}, {}", prefix, args.join(", "), &user[1..])
+            }
+            Some(user) => format!("{}{} {}", prefix, args.join(" "), user),
+        }
+    }
+
     pub enum Backend {
         Msvc,
         Ninja,
@@ -200,11 +813,20 @@ mod build {
             // MSVC.
             } else if build_cfg!(feature = "build-with-ninja") {
                 Self::Ninja
+            } else if build_cfg!(feature = "auto-backend") {
+                let backend = Self::detect();
+                println!(
+                    "cargo:warning=no `build-with-*` feature is enabled; building libui with {}",
+                    backend.as_str(),
+                );
+
+                backend
             } else {
                 panic!(
                     "
                     The `build` feature is enabled but no `build-with-*` feature is not enabled. \
-                    *libui-ng-sys* doesn't know which build backend to use. \
+                    *libui-ng-sys* doesn't know which build backend to use. Enable \
+                    `auto-backend` to have one detected automatically. \
                     "
                 );
             }
@@ -212,6 +834,25 @@ mod build {
     }
 
     impl Backend {
+        /// Picks the most suitable backend available on this machine.
+        ///
+        /// MSVC is preferred when targeting MSVC from a Visual Studio environment, as is Xcode when
+        /// targeting macOS with Xcode installed. Otherwise, we fall back to Ninja, which is built
+        /// from source if need be.
+        fn detect() -> Self {
+            if build_cfg!(target_env = "msvc")
+                && (config::var("VSINSTALLDIR").is_some() || config::find_program("cl").is_some())
+            {
+                Self::Msvc
+            } else if build_cfg!(target_os = "macos")
+                && config::find_program("xcodebuild").is_some()
+            {
+                Self::Xcode
+            } else {
+                Self::Ninja
+            }
+        }
+
         /// Builds *libui*.
         pub fn build_libui(
             self,
@@ -219,40 +860,296 @@ mod build {
             meson_dir: &Path,
             ninja_dir: &Path,
         ) -> Result<(), Error> {
-            if Self::libui_path(libui_dir).exists() {
-                // We'll give the benefit of the doubt that this is actually a complete, working
-                // library.
-                return Ok(());
+            if Self::is_cache_enabled() {
+                if Self::libui_path(libui_dir).exists() {
+                    // We'll give the benefit of the doubt that this is actually a complete, working
+                    // library.
+                    return Ok(());
+                }
+            } else {
+                // Meson refuses to set up a build directory that is already configured, so we
+                // start from scratch.
+                Self::remove_build_dir(libui_dir).map_err(Error::CleanLibui)?;
             }
 
             if let Self::Ninja = self {
                 // This must precede setting up *libui* as Meson requires Ninja even in the
                 // configuration phase.
-                Self::build_ninja(ninja_dir).map_err(Error::BuildNinja)?;
+                log::step("build Ninja", || Self::build_ninja(ninja_dir))
+                    .map_err(Error::BuildNinja)?;
             }
 
-            self.setup_libui(libui_dir, meson_dir, ninja_dir).map_err(Error::SetupLibui)?;
-            self.compile_libui(libui_dir, meson_dir, ninja_dir)
-                .map_err(Error::CompileLibui)?;
+            let build_dir = libui_dir.join("build");
+            if Self::is_universal() {
+                if !matches!(self, Self::Ninja) {
+                    panic!("`universal-macos` requires building libui with Ninja");
+                }
+
+                let mut archives = Vec::new();
+                for arch in UNIVERSAL_ARCHS {
+                    let arch_dir = libui_dir.join(format!("build-{}", arch.name));
+                    let cross_file = libui_dir.join(format!("cross-{}.ini", arch.name));
+                    fs::write(&cross_file, self.cross_file_contents(arch))
+                        .map_err(Error::WriteCrossFile)?;
+
+                    log::step(&format!("set up libui for {}", arch.name), || {
+                        let cross = Some((cross_file.as_path(), arch));
+                        self.setup_libui(libui_dir, &arch_dir, cross, meson_dir, ninja_dir)
+                    })
+                    .map_err(Error::SetupLibui)?;
+                    log::step(&format!("compile libui for {}", arch.name), || {
+                        self.compile_libui(&arch_dir, meson_dir, ninja_dir)
+                    })
+                    .map_err(Error::CompileLibui)?;
+
+                    archives.push(arch_dir.join("meson-out"));
+                }
+
+                Self::combine_archs(&archives, &build_dir.join("meson-out"))
+                    .map_err(Error::CombineArchs)?;
+            } else {
+                log::step("set up libui", || {
+                    self.setup_libui(libui_dir, &build_dir, None, meson_dir, ninja_dir)
+                })
+                .map_err(Error::SetupLibui)?;
+                log::step("compile libui", || self.compile_libui(&build_dir, meson_dir, ninja_dir))
+                    .map_err(Error::CompileLibui)?;
+            }
+            if cfg!(feature = "strip-libui") {
+                self.strip_libui(libui_dir);
+            }
+            if config::flag("LIBUI_NG_SYS_VERIFY") == Some(true) {
+                Self::verify_libui(libui_dir)?;
+            }
+            if cfg!(feature = "build-both") {
+                Self::verify_shared_exports(libui_dir)?;
+            }
             self.rename_libui(libui_dir).map_err(Error::RenameLibui)?;
 
             Ok(())
         }
 
+        /// Determines if previously-built artifacts in `$OUT_DIR` may be reused.
+        ///
+        /// Validation pipelines may set `$LIBUI_NG_SYS_NO_CACHE` to force every build to start
+        /// from scratch.
+        fn is_cache_enabled() -> bool {
+            config::flag("LIBUI_NG_SYS_NO_CACHE") != Some(true)
+        }
+
+        fn remove_build_dir(libui_dir: &Path) -> io::Result<()> {
+            let build_dirs = iter::once(String::from("build"))
+                .chain(UNIVERSAL_ARCHS.iter().map(|arch| format!("build-{}", arch.name)));
+            for build_dir in build_dirs {
+                let build_dir = libui_dir.join(build_dir);
+                if build_dir.exists() {
+                    fs::remove_dir_all(build_dir)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Determines if *libui* should be built for every architecture in [`UNIVERSAL_ARCHS`]
+        /// and combined into universal binaries.
+        fn is_universal() -> bool {
+            cfg!(feature = "universal-macos") && build_cfg!(target_os = "macos")
+        }
+
+        /// Returns a Meson cross file that compiles for `arch`.
+        ///
+        /// The compilers are those that [`Self::compiler_env`] would select.
+        fn cross_file_contents(&self, arch: &MacosArch) -> String {
+            let compilers = self.compiler_env();
+            let compiler = |key: &str, default: &str| {
+                let compiler = compilers
+                    .iter()
+                    .find(|(it, _)| *it == key)
+                    .map_or(default, |(_, compiler)| compiler.as_str());
+
+                // A compiler cache is given as the first word.
+                let words = compiler
+                    .split_whitespace()
+                    .map(|word| format!("'{}'", word))
+                    .collect::<Vec<_>>();
+
+                format!("[{}]", words.join(", "))
+            };
+            let (cc, cxx) = (compiler("CC", "cc"), compiler("CXX", "c++"));
+
+            // The `-arch` flags are not set here but by `setup_libui`, as options given on the
+            // command line, e.g., in `$LIBUI_NG_SYS_MESON_OPTS`, would replace them.
+            format!(
+                "\
+                    [binaries]\n\
+                    c = {cc}\n\
+                    cpp = {cxx}\n\
+                    objc = {cc}\n\
+                    ar = 'ar'\n\
+                    strip = 'strip'\n\
+                    \n\
+                    [host_machine]\n\
+                    system = 'darwin'\n\
+                    cpu_family = '{cpu_family}'\n\
+                    cpu = '{cpu}'\n\
+                    endian = 'little'\n\
+                ",
+                cc = cc,
+                cxx = cxx,
+                cpu_family = arch.cpu_family,
+                cpu = arch.name,
+            )
+        }
+
+        /// Combines the libraries built for each architecture, found in `arch_dirs`, into
+        /// universal libraries in `to` with `lipo`.
+        ///
+        /// Only regular files are combined; symbolic links, such as versioned aliases of the
+        /// shared *libui*, are skipped.
+        fn combine_archs(arch_dirs: &[PathBuf], to: &Path) -> io::Result<()> {
+            let Some(first_dir) = arch_dirs.first() else {
+                return Ok(());
+            };
+
+            fs::create_dir_all(to)?;
+            for entry in fs::read_dir(first_dir)? {
+                let entry = entry?;
+                let name = entry.file_name();
+                let is_library = name
+                    .to_str()
+                    .is_some_and(|name| name.ends_with(".a") || name.ends_with(".dylib"));
+                if !is_library || !entry.file_type()?.is_file() {
+                    continue;
+                }
+
+                let status = process::Command::new("lipo")
+                    .arg("-create")
+                    .args(arch_dirs.iter().map(|dir| dir.join(&name)))
+                    .arg("-output")
+                    .arg(to.join(&name))
+                    .status()?;
+                if !status.success() {
+                    return Err(io::Error::other(format!("lipo failed to combine {:?}", name)));
+                }
+            }
+
+            Ok(())
+        }
+
         fn libui_path(libui_dir: &Path) -> PathBuf {
             libui_dir.join("libui.a")
         }
 
+        /// Returns the path to the Ninja used to build *libui*, preferring an installed Ninja
+        /// over the one bootstrapped in `ninja_dir`.
         fn ninja_path(ninja_dir: &Path) -> PathBuf {
-            let ext = env::consts::EXE_EXTENSION;
-            ninja_dir.join("ninja").with_extension(ext)
+            Self::system_ninja().unwrap_or_else(|| {
+                let ext = env::consts::EXE_EXTENSION;
+                ninja_dir.join("ninja").with_extension(ext)
+            })
+        }
+
+        /// Finds an installed Ninja that needn't be bootstrapped from source.
+        ///
+        /// `$LIBUI_NG_SYS_NINJA` takes precedence over a `ninja` found in `$PATH`. Either must
+        /// successfully report its version to be used.
+        pub fn system_ninja() -> Option<PathBuf> {
+            static NINJA: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+            NINJA
+                .get_or_init(|| {
+                    let requested = config::var("LIBUI_NG_SYS_NINJA").map(PathBuf::from);
+                    let ninja = requested.clone().or_else(|| config::find_program("ninja"))?;
+                    let works = process::Command::new(&ninja)
+                        .arg("--version")
+                        .output()
+                        .is_ok_and(|out| out.status.success());
+                    if works {
+                        return Some(ninja);
+                    }
+
+                    if requested.is_some() {
+                        println!(
+                            "cargo:warning=$LIBUI_NG_SYS_NINJA does not name a working Ninja; \
+                            bootstrapping one instead"
+                        );
+                    }
+
+                    None
+                })
+                .clone()
         }
 
         fn run_python(
             f: impl Fn(&mut process::Command),
             ninja_dir: Option<&Path>,
         ) -> Result<(), PythonError> {
-            let mut cmd = process::Command::new("python3");
+            Self::run(process::Command::new("python3"), f, ninja_dir)
+        }
+
+        /// Runs Meson, preferring an installed Meson over the one synced to `meson_dir`.
+        fn run_meson(
+            &self,
+            meson_dir: &Path,
+            f: impl Fn(&mut process::Command),
+            ninja_dir: Option<&Path>,
+        ) -> Result<(), PythonError> {
+            let cmd = match self.system_meson() {
+                Some(meson) => process::Command::new(meson),
+                None => {
+                    let mut cmd = process::Command::new("python3");
+                    cmd.arg(meson_dir.join("meson.py"));
+                    cmd
+                }
+            };
+
+            Self::run(cmd, f, ninja_dir)
+        }
+
+        /// Finds an installed Meson that is recent enough to build *libui* with this backend.
+        ///
+        /// `$LIBUI_NG_SYS_MESON` takes precedence over a `meson` found in `$PATH`.
+        pub fn system_meson(&self) -> Option<PathBuf> {
+            static MESON: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+            let min_version = match self {
+                Self::Xcode => MIN_MESON_XCODE_VERSION,
+                _ => MIN_MESON_VERSION,
+            };
+
+            MESON
+                .get_or_init(|| {
+                    let requested = config::var("LIBUI_NG_SYS_MESON").map(PathBuf::from);
+                    let meson = requested.clone().or_else(|| config::find_program("meson"))?;
+                    let is_recent = process::Command::new(&meson)
+                        .arg("--version")
+                        .output()
+                        .ok()
+                        .filter(|out| out.status.success())
+                        .and_then(|out| version::parse(&String::from_utf8_lossy(&out.stdout)))
+                        .is_some_and(|version| Some(version) >= version::parse(min_version));
+                    if is_recent {
+                        return Some(meson);
+                    }
+
+                    if requested.is_some() {
+                        println!(
+                            "cargo:warning=$LIBUI_NG_SYS_MESON does not name a working Meson \
+                            v{} or newer; using the bundled Meson instead",
+                            min_version,
+                        );
+                    }
+
+                    None
+                })
+                .clone()
+        }
+
+        fn run(
+            mut cmd: process::Command,
+            f: impl Fn(&mut process::Command),
+            ninja_dir: Option<&Path>,
+        ) -> Result<(), PythonError> {
             f(&mut cmd);
 
             if let Some(dir) = ninja_dir {
@@ -269,12 +1166,26 @@ mod build {
 
         /// Builds Ninja.
         fn build_ninja(ninja_dir: &Path) -> Result<(), PythonError> {
-            if Self::ninja_path(ninja_dir).exists() {
+            if Self::system_ninja().is_some() {
+                return Ok(());
+            }
+            if Self::is_cache_enabled() && Self::ninja_path(ninja_dir).exists() {
                 // We'll give the benefit of the doubt that this is actually a complete, working
                 // binary.
                 return Ok(());
             }
 
+            let ninja_path = Self::ninja_path(ninja_dir);
+            let cached_path = Self::cached_ninja_path();
+            if let Some(ref cached_path) = cached_path {
+                if Self::is_cache_enabled()
+                    && cached_path.is_file()
+                    && fs::copy(cached_path, &ninja_path).is_ok()
+                {
+                    return Ok(());
+                }
+            }
+
             Self::run_python(
                 |cmd| {
                     cmd
@@ -283,35 +1194,285 @@ mod build {
                         .current_dir(ninja_dir);
                 },
                 None,
-            )
+            )?;
+
+            if let Some(cached_path) = cached_path {
+                // The cache only saves time, so failing to fill it is no reason to fail the build.
+                let stored = cached_path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|()| fs::copy(&ninja_path, &cached_path));
+                if let Err(e) = stored {
+                    println!(
+                        "cargo:warning=failed to cache Ninja at {}: {}",
+                        cached_path.display(),
+                        e,
+                    );
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Returns where a bootstrapped Ninja is kept across `cargo clean`, if
+        /// `$LIBUI_NG_SYS_CACHE_DIR` is set.
+        ///
+        /// The cached binary is keyed by the host triple, as Ninja runs on the host, and by the
+        /// version of this crate, so that upgrading it never reuses a Ninja bootstrapped by an
+        /// older release.
+        fn cached_ninja_path() -> Option<PathBuf> {
+            let cache_dir = PathBuf::from(config::var("LIBUI_NG_SYS_CACHE_DIR")?);
+            let key = format!(
+                "{}-{}",
+                env::var("CARGO_PKG_VERSION").unwrap(),
+                env::var("HOST").unwrap(),
+            );
+            let filename = Path::new("ninja").with_extension(env::consts::EXE_EXTENSION);
+
+            Some(cache_dir.join("ninja").join(key).join(filename))
         }
 
-        /// Prepares *libui* to be built.
+        /// Prepares *libui* to be built in `build_dir`, cross-compiling for the given architecture
+        /// with the given cross file if any.
         fn setup_libui(
             &self,
             libui_dir: &Path,
+            build_dir: &Path,
+            cross: Option<(&Path, &MacosArch)>,
             meson_dir: &Path,
             ninja_dir: &Path,
         ) -> Result<(), PythonError> {
-            Self::run_python(
+            // Advanced users may set *libui*'s own project options, which we pass through
+            // verbatim unless we must merge our own values into them.
+            let mut user_opts = config::list("LIBUI_NG_SYS_MESON_OPTS");
+            let mut flags = Vec::new();
+            if let Some((_, arch)) = cross {
+                flags.extend([String::from("-arch"), arch.name.to_string()]);
+            }
+            // *libui* may otherwise target an older or newer macOS than the binary that links it.
+            if let Some(version) = macos::deployment_target() {
+                flags.push(format!("-mmacosx-version-min={}", version));
+            }
+            let mut flag_opts = Vec::new();
+            if !flags.is_empty() {
+                for option in [
+                    "c_args",
+                    "cpp_args",
+                    "objc_args",
+                    "c_link_args",
+                    "cpp_link_args",
+                    "objc_link_args",
+                ] {
+                    flag_opts.push(merge_array_option(&mut user_opts, option, &flags));
+                }
+            }
+
+            self.run_meson(
+                meson_dir,
                 |cmd| {
+                    cmd.envs(self.compiler_env());
                     cmd
-                        .arg(meson_dir.join("meson.py"))
                         .arg("setup")
-                        .arg("--default-library=static")
+                        .arg(format!("--default-library={}", Self::default_library()))
                         .arg("--buildtype=release")
                         .arg(format!("--optimization={}", Self::optimization_level()))
-                        .arg(format!("--backend={}", self.as_str()))
+                        .arg(format!("-Db_lto={}", Self::uses_lto()))
+                        .arg(format!("--backend={}", self.meson_backend()))
+                        // Unity builds are opt-in as they may hide warnings.
+                        .arg(format!("--unity={}", Self::unity()))
                         // It's OK that this option is hardcoded (which is MSVC-specific) for all
                         // backends; Meson will simply ignore it if MSVC isn't the selected backend.
-                        .arg("-Db_vscrt=from_buildtype")
-                        .arg(libui_dir.join("build"))
+                        .arg("-Db_vscrt=from_buildtype");
+
+                    // New compiler releases tend to introduce warnings, which must not break
+                    // the build should *libui* ever enable `werror`. `permissive-warnings` goes
+                    // further, silencing all but the warnings the compiler emits by default.
+                    cmd.arg("-Dwerror=false");
+                    if cfg!(feature = "permissive-warnings") {
+                        cmd.arg("-Dwarning_level=0");
+                    }
+
+                    if cfg!(feature = "pic") {
+                        // Otherwise, the static library may not link into a shared object.
+                        cmd.arg("-Db_staticpic=true");
+                    }
+
+                    if let Some((cross_file, _)) = cross {
+                        cmd.arg("--cross-file").arg(cross_file);
+                    }
+                    cmd.args(&flag_opts);
+
+                    if build_cfg!(target_os = "linux") {
+                        // This keeps Meson and bindgen in agreement about which GTK to use.
+                        if let Some(dir) = gtk::pkg_config_dir() {
+                            cmd.arg(format!("-Dpkg_config_path={}", dir.display()));
+                        }
+                    }
+
+                    cmd.args(&user_opts);
+
+                    cmd
+                        .arg(build_dir)
                         .arg(libui_dir);
                 },
                 Some(ninja_dir),
             )
         }
 
+        /// Returns the value of Meson's `--unity` option, which `$LIBUI_NG_SYS_UNITY` may set to
+        /// `on`.
+        fn unity() -> &'static str {
+            if config::flag("LIBUI_NG_SYS_UNITY") == Some(true) {
+                "on"
+            } else {
+                "off"
+            }
+        }
+
+        /// Returns the kind of library Meson should produce.
+        ///
+        /// The Rust crate always links the static archive, but plugin hosts may want a shared
+        /// object as well.
+        fn default_library() -> &'static str {
+            if cfg!(feature = "build-both") {
+                "both"
+            } else {
+                "static"
+            }
+        }
+
+        /// Copies the shared *libui*, along with any versioned aliases, from the build directory
+        /// to `to`.
+        ///
+        /// This only makes sense when the `build-both` feature is enabled.
+        pub fn export_shared_libui(libui_dir: &Path, to: &Path) -> Result<(), Error> {
+            let is_shared_libui = |name: &str| {
+                let Some(ext) = name.strip_prefix("libui.").or_else(|| name.strip_prefix("ui."))
+                else {
+                    return false;
+                };
+
+                if build_cfg!(target_os = "macos") {
+                    ext.ends_with("dylib")
+                } else if build_cfg!(target_os = "windows") {
+                    ext == "dll"
+                } else {
+                    // Shared objects may be suffixed with a version, e.g., "libui.so.0".
+                    ext == "so" || ext.starts_with("so.")
+                }
+            };
+
+            let export = || -> io::Result<()> {
+                fs::create_dir_all(to)?;
+
+                let mut found = false;
+                for entry in fs::read_dir(libui_dir.join("build/meson-out"))? {
+                    let entry = entry?;
+                    let name = entry.file_name();
+                    if name.to_str().is_some_and(is_shared_libui) {
+                        // This follows symbolic links, so aliases become full copies.
+                        fs::copy(entry.path(), to.join(&name))?;
+                        found = true;
+                    }
+                }
+
+                if found {
+                    Ok(())
+                } else {
+                    Err(io::Error::new(io::ErrorKind::NotFound, "no shared libui was built"))
+                }
+            };
+
+            export().map_err(Error::ExportSharedLibui)
+        }
+
+        /// Returns the environment variables that select the C and C++ compilers used by Meson.
+        ///
+        /// The compilers may be chosen explicitly with `$LIBUI_NG_SYS_COMPILER` and are wrapped in
+        /// a compiler cache when one is available. Only the Ninja and Xcode backends respect these
+        /// variables; MSVC selects its own compiler, and compiler caches are only supported with
+        /// Ninja.
+        fn compiler_env(&self) -> Vec<(&'static str, String)> {
+            let compilers = match self {
+                Self::Msvc => return Vec::new(),
+                Self::Ninja | Self::Xcode => Self::requested_compilers(),
+            };
+            let launcher = match self {
+                Self::Ninja => Self::compiler_launcher(),
+                Self::Msvc | Self::Xcode => None,
+            };
+            if compilers.is_none() && launcher.is_none() {
+                return Vec::new();
+            }
+
+            let (cc, cxx) = compilers.unzip();
+
+            [("CC", cc, "cc", "cl"), ("CXX", cxx, "c++", "cl")]
+                .into_iter()
+                .map(|(key, requested, unix_default, windows_default)| {
+                    let compiler = requested
+                        .map(String::from)
+                        .or_else(|| env::var(key).ok())
+                        .unwrap_or_else(|| {
+                            if build_cfg!(target_os = "windows") {
+                                windows_default.to_string()
+                            } else {
+                                unix_default.to_string()
+                            }
+                        });
+
+                    match launcher {
+                        Some(ref launcher) => (key, format!("{} {}", launcher.display(), compiler)),
+                        None => (key, compiler),
+                    }
+                })
+                .collect()
+        }
+
+        /// Returns the C and C++ compilers requested with `$LIBUI_NG_SYS_COMPILER`, if any.
+        fn requested_compilers() -> Option<(&'static str, &'static str)> {
+            match config::var("LIBUI_NG_SYS_COMPILER")?.as_str() {
+                "gcc" => Some(("gcc", "g++")),
+                "clang" => Some(("clang", "clang++")),
+                other => panic!(
+                    "$LIBUI_NG_SYS_COMPILER must be either `gcc` or `clang`, not `{}`",
+                    other,
+                ),
+            }
+        }
+
+        /// Detects a compiler cache (*sccache* or *ccache*) with which to wrap the compilers.
+        ///
+        /// `$LIBUI_NG_SYS_CCACHE` may be set to `0` to disable detection entirely, or to `1` to
+        /// warn when no compiler cache can be found.
+        fn compiler_launcher() -> Option<PathBuf> {
+            let requested = config::flag("LIBUI_NG_SYS_CCACHE");
+            if requested == Some(false) {
+                return None;
+            }
+
+            // If Cargo is already wrapping `rustc` in a compiler cache, we'll reuse it.
+            let launcher = config::var("RUSTC_WRAPPER")
+                .map(PathBuf::from)
+                .filter(|path| {
+                    matches!(
+                        path.file_stem().and_then(|stem| stem.to_str()),
+                        Some("sccache" | "ccache"),
+                    )
+                })
+                .or_else(|| config::find_program("sccache"))
+                .or_else(|| config::find_program("ccache"));
+
+            if launcher.is_none() && requested == Some(true) {
+                println!(
+                    "cargo:warning=$LIBUI_NG_SYS_CCACHE is set, but neither sccache nor ccache \
+                    could be found"
+                );
+            }
+
+            launcher
+        }
+
         // This may be used at some point.
         #[allow(dead_code)]
         fn is_debug() -> bool {
@@ -320,6 +1481,10 @@ mod build {
 
         fn optimization_level() -> String {
             let level = env::var("OPT_LEVEL").expect("$OPT_LEVEL is unset");
+            if level != "0" && Self::prefers_small_size() {
+                return String::from("s");
+            }
+
             match level.as_str() {
                 // Meson doesn't support "-Oz"; we'll try the next-closest option.
                 "z" => String::from("s"),
@@ -327,7 +1492,65 @@ mod build {
             }
         }
 
-        fn as_str(&self) -> &'static str {
+        /// Translates [`Self::optimization_level`] into a value of Xcode's
+        /// `GCC_OPTIMIZATION_LEVEL` build setting.
+        fn xcode_optimization_level() -> String {
+            match Self::optimization_level().as_str() {
+                // Xcode has no equivalent to "-Og".
+                "g" => String::from("0"),
+                level => level.to_string(),
+            }
+        }
+
+        /// Determines if *libui* should be compiled with link-time optimization.
+        ///
+        /// Cargo doesn't tell build scripts whether the profile enables LTO, and LTO objects are
+        /// only usable by a compatible linker, so this is opt-in through `$LIBUI_NG_SYS_LTO`.
+        fn uses_lto() -> bool {
+            config::flag("LIBUI_NG_SYS_LTO") == Some(true)
+        }
+
+        /// Determines if *libui* should be optimized for size rather than speed.
+        ///
+        /// This is the case for targets that are typically used to produce small, self-contained
+        /// binaries, such as musl-based distributions. `$LIBUI_NG_SYS_OPTIMIZE_SIZE` overrides this
+        /// decision in either direction.
+        fn prefers_small_size() -> bool {
+            config::flag("LIBUI_NG_SYS_OPTIMIZE_SIZE")
+                .unwrap_or_else(|| build_cfg!(target_env = "musl"))
+        }
+
+        /// Returns the value of Meson's `--backend` option.
+        ///
+        /// This is normally [`Self::as_str`], but `$LIBUI_NG_SYS_MESON_BACKEND` may name a more
+        /// specific Meson backend, e.g., `vs2022`, as long as it belongs to the selected backend.
+        fn meson_backend(&self) -> String {
+            let Some(requested) = config::var("LIBUI_NG_SYS_MESON_BACKEND") else {
+                return self.as_str().to_string();
+            };
+
+            let is_compatible = match self {
+                Self::Msvc => matches!(
+                    requested.as_str(),
+                    "vs" | "vs2010" | "vs2012" | "vs2013" | "vs2015" | "vs2017" | "vs2019"
+                        | "vs2022",
+                ),
+                Self::Ninja => requested == "ninja",
+                Self::Xcode => requested == "xcode",
+            };
+            if !is_compatible {
+                panic!(
+                    "$LIBUI_NG_SYS_MESON_BACKEND must name a Meson backend compatible with `{}`, \
+                    not `{}`",
+                    self.as_str(),
+                    requested,
+                );
+            }
+
+            requested
+        }
+
+        pub fn as_str(&self) -> &'static str {
             match self {
                 Self::Msvc => "vs",
                 Self::Ninja => "ninja",
@@ -337,21 +1560,168 @@ mod build {
 
         fn compile_libui(
             &self,
-            libui_dir: &Path,
+            build_dir: &Path,
             meson_dir: &Path,
             ninja_dir: &Path,
         ) -> Result<(), PythonError> {
-            Self::run_python(
+            self.run_meson(
+                meson_dir,
                 |cmd| {
                     cmd
-                        .arg(meson_dir.join("meson.py"))
                         .arg("compile")
-                        .arg(format!("-C={}", libui_dir.join("build").display()));
+                        .arg(format!("-C={}", build_dir.display()));
+
+                    if let Self::Xcode = self {
+                        // Xcode takes its optimization level from the build configuration rather
+                        // than the flags Meson generates, so we must override it explicitly.
+                        cmd.arg(format!(
+                            "--xcode-args=GCC_OPTIMIZATION_LEVEL={}",
+                            Self::xcode_optimization_level(),
+                        ));
+                    }
                 },
                 Some(ninja_dir),
             )
         }
 
+        /// Strips debug information from the compiled *libui* archive.
+        ///
+        /// This is best-effort: if no suitable `strip` can be found or it fails, a warning is
+        /// emitted and the archive is left as-is.
+        fn strip_libui(&self, libui_dir: &Path) {
+            if let Self::Msvc = self {
+                // MSVC keeps debug information in separate PDB files.
+                return;
+            }
+
+            let Some(strip) = config::find_program("llvm-strip")
+                .or_else(|| config::find_program("strip"))
+            else {
+                println!("cargo:warning=`strip-libui` is enabled, but no `strip` was found");
+                return;
+            };
+
+            // Apple's `strip` doesn't understand `--strip-debug`.
+            let flag = if build_cfg!(target_os = "macos") && !strip.ends_with("llvm-strip") {
+                "-S"
+            } else {
+                "--strip-debug"
+            };
+
+            let status = process::Command::new(&strip)
+                .arg(flag)
+                .arg(libui_dir.join("build/meson-out/libui.a"))
+                .status();
+            if !matches!(status, Ok(status) if status.success()) {
+                println!("cargo:warning=failed to strip libui with `{}`", strip.display());
+            }
+        }
+
+        /// Checks that the compiled *libui* archive defines a few sentinel symbols.
+        ///
+        /// This catches a broken build before Rust attempts to link against it.
+        fn verify_libui(libui_dir: &Path) -> Result<(), Error> {
+            static SENTINEL_SYMBOLS: &[&str] = &["uiInit", "uiMain", "uiQuit", "uiUninit"];
+
+            let symbols = Self::defined_symbols(&libui_dir.join("build/meson-out/libui.a"), false)
+                .map_err(Error::InspectLibui)?;
+
+            let missing = SENTINEL_SYMBOLS
+                .iter()
+                .filter(|sentinel| !symbols.iter().any(|symbol| symbol == *sentinel))
+                .map(|sentinel| sentinel.to_string())
+                .collect::<Vec<String>>();
+
+            if missing.is_empty() {
+                Ok(())
+            } else {
+                Err(Error::MissingSymbols(missing))
+            }
+        }
+
+        /// Checks that the shared *libui* exports every `ui*` symbol that the static archive
+        /// defines.
+        ///
+        /// *libui* is compiled with `-fvisibility=hidden`, so a function missing its export
+        /// annotation would link fine statically but fail to load dynamically. If `nm` is
+        /// unavailable, a warning is emitted and the check is skipped.
+        fn verify_shared_exports(libui_dir: &Path) -> Result<(), Error> {
+            if build_cfg!(target_os = "windows") {
+                // DLL exports are explicit, and `nm` cannot read MSVC binaries anyway.
+                return Ok(());
+            }
+
+            let build_dir = libui_dir.join("build/meson-out");
+            let shared_path = if build_cfg!(target_os = "macos") {
+                build_dir.join("libui.dylib")
+            } else {
+                build_dir.join("libui.so")
+            };
+
+            let symbols = || -> io::Result<_> {
+                let defined = Self::defined_symbols(&build_dir.join("libui.a"), false)?;
+                let exported = Self::defined_symbols(&shared_path, true)?;
+
+                Ok((defined, exported))
+            };
+            let (defined, exported) = match symbols() {
+                Ok(symbols) => symbols,
+                Err(e) => {
+                    println!("cargo:warning=failed to check the exports of libui: {}", e);
+                    return Ok(());
+                }
+            };
+
+            let mut unexported = defined
+                .into_iter()
+                .filter(|symbol| Self::is_libui_symbol(symbol))
+                .filter(|symbol| !exported.contains(symbol))
+                .collect::<Vec<String>>();
+            unexported.sort();
+            unexported.dedup();
+
+            if unexported.is_empty() {
+                Ok(())
+            } else {
+                Err(Error::UnexportedSymbols(unexported))
+            }
+        }
+
+        /// Determines if `symbol` is part of the *libui* API, i.e., would be matched by the
+        /// bindings' allowlist.
+        fn is_libui_symbol(symbol: &str) -> bool {
+            symbol
+                .strip_prefix("ui")
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|c| c.is_ascii_uppercase())
+        }
+
+        /// Lists the external symbols defined by the object file at `path`.
+        ///
+        /// If `dynamic` is `true`, the dynamic symbol table is read instead, which for a shared
+        /// object lists its exports.
+        fn defined_symbols(path: &Path, dynamic: bool) -> io::Result<Vec<String>> {
+            let mut cmd = process::Command::new("nm");
+            cmd.arg("--defined-only").arg("--extern-only");
+            if dynamic {
+                cmd.arg("--dynamic");
+            }
+
+            let out = cmd.arg(path).output()?;
+            if !out.status.success() {
+                return Err(io::Error::other(String::from_utf8_lossy(&out.stderr).into_owned()));
+            }
+
+            let symbols = String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .filter_map(|line| line.split_whitespace().last())
+                // Mach-O symbols are prefixed with an underscore.
+                .map(|symbol| symbol.strip_prefix('_').unwrap_or(symbol).to_string())
+                .collect();
+
+            Ok(symbols)
+        }
+
         fn rename_libui(&self, libui_dir: &Path) -> Result<(), io::Error> {
             // Meson unconditionally names the library "libui.a", which prevents MSVC's `link.exe`
             // from finding it; we must manually rename it to "ui.lib".
@@ -366,7 +1736,20 @@ mod build {
 }
 
 mod bindings {
-    use std::{fmt, io, path::Path};
+    use std::{
+        collections::hash_map::DefaultHasher,
+        env,
+        fmt,
+        fs,
+        hash::{Hash, Hasher},
+        io,
+        iter,
+        path::{Path, PathBuf},
+        process,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{config, depfile, gtk, macos, summary::Summary};
 
     /// The error type returned by binding functions.
     #[derive(Debug)]
@@ -375,29 +1758,342 @@ mod bindings {
         Generate,
         /// Failed to write bindings to a file.
         WriteToFile(io::Error),
+        /// Failed to copy bindings to `$LIBUI_NG_SYS_EMIT_BINDINGS_TO`.
+        Emit(io::Error),
+        /// Failed to copy the committed bindings to `$OUT_DIR`.
+        CopyCommitted(io::Error),
+        /// Failed to read the control signatures defined by *libui*.
+        ReadControlSigs(io::Error),
+        /// Failed to read a *libui* header.
+        ReadHeader(io::Error),
+        /// Failed to read back generated bindings.
+        ReadBindings(io::Error),
+        /// bindgen dropped the given functions declared by *libui*.
+        MissingFunctions(Vec<String>),
     }
 
+    /// The prefix shared by the filenames of all generated bindings unless overridden by
+    /// `$LIBUI_NG_SYS_BINDINGS_PREFIX`.
+    static DEFAULT_FILENAME_PREFIX: &str = "bindings";
+
     /// Generates bindings to *libui* and writes them to the given directory.
-    pub fn generate(libui_dir: &Path, out_dir: &Path) -> Result<(), Error> {
-        Header::main().generate(libui_dir, out_dir)?;
-        Header::control_sigs().generate(libui_dir, out_dir)?;
+    pub fn generate(
+        libui_dir: &Path,
+        out_dir: &Path,
+        summary: &mut Summary,
+    ) -> Result<(), Error> {
+        let (prefix, stem) = filename_stem();
+
+        // During development, a single header may be regenerated on demand without touching the
+        // others.
+        let only = config::var("LIBUI_NG_SYS_ONLY");
+
+        let headers = headers();
+
+        // Probing for system headers can be slow, so we only do it once rather than per header.
+        let clang_args = ClangArgs::new();
+        summary.include_paths.extend(clang_args.include_paths.iter().cloned());
+        let clang_args = clang_args.as_args();
+
+        let mut filenames = vec![format!("{}-info.rs", stem)];
+        for header in headers {
+            let header = header.with_filename_prefix(&stem);
+            filenames.push(header.filename());
+
+            // The target triple is omitted when naming a header to regenerate.
+            let freshness = match only {
+                Some(ref name) if *name == header.name().replacen(&stem, &prefix, 1) => {
+                    Freshness::Stale
+                }
+                Some(_) => Freshness::Exists,
+                None => Freshness::Fingerprint,
+            };
+            header.generate(libui_dir, out_dir, &clang_args, freshness)?;
+        }
 
-        if build_cfg!(target_os = "macos") {
-            Header::darwin().generate(libui_dir, out_dir)?;
+        // The main bindings may not have been regenerated, so we read the functions back from
+        // them rather than collecting them as bindgen runs.
+        let main_path = out_dir.join(Header::main().with_filename_prefix(&stem).filename());
+        let functions =
+            function_names(&fs::read_to_string(main_path).map_err(Error::ReadBindings)?);
+        let control_sigs = control_sig_names(libui_dir).map_err(Error::ReadControlSigs)?;
+        write_info(out_dir, &stem, &control_sigs, &functions)?;
+
+        // Downstream maintainers may wish to commit the generated bindings, e.g., to the
+        // `bindings` directory for use with the `prebuilt-bindings` feature.
+        if let Some(dir) = config::var("LIBUI_NG_SYS_EMIT_BINDINGS_TO") {
+            // The emitted copies drop the target triple, as the `prebuilt-bindings` feature
+            // expects.
+            let renames = filenames
+                .iter()
+                .map(|filename| (filename.as_str(), filename.replacen(&stem, &prefix, 1)))
+                .collect::<Vec<_>>();
+            emit(out_dir, Path::new(&dir), &renames).map_err(Error::Emit)?;
         }
-        if build_cfg!(target_os = "linux") {
-            Header::unix().generate(libui_dir, out_dir)?;
+
+        Ok(())
+    }
+
+    /// Determines if empty stubs are to be written in place of the real bindings.
+    pub fn writes_stubs() -> bool {
+        config::flag("LIBUI_NG_SYS_STUB_BINDINGS") == Some(true)
+    }
+
+    /// Writes empty stubs in place of the bindings for every header to `out_dir`.
+    ///
+    /// Unlike [`generate`], this reads nothing from *libui*, so neither Clang nor the submodules
+    /// are needed.
+    pub fn write_stubs(out_dir: &Path) -> Result<(), Error> {
+        println!("cargo:warning=writing stub bindings; the crate will not be usable");
+        let (_, stem) = filename_stem();
+        for header in headers() {
+            header.with_filename_prefix(&stem).write_stub(out_dir)?;
         }
-        if build_cfg!(target_os = "windows") {
-            Header::windows().generate(libui_dir, out_dir)?;
+
+        // Stub bindings don't define the signature constants that the entries would refer to.
+        write_info(out_dir, &stem, &[], &[])
+    }
+
+    /// Copies the bindings committed to the `bindings` directory of this crate to `out_dir`, in
+    /// place of generating them.
+    ///
+    /// This is used on *docs.rs*, where Clang and the full header tree may be unavailable.
+    pub fn copy_committed(out_dir: &Path) -> Result<(), Error> {
+        let (_, stem) = filename_stem();
+        let copy = || -> io::Result<()> {
+            for suffix in committed_suffixes() {
+                fs::copy(
+                    committed_path(&suffix),
+                    out_dir.join(format!("{}{}.rs", stem, suffix)),
+                )?;
+            }
+
+            Ok(())
+        };
+
+        copy().map_err(Error::CopyCommitted)
+    }
+
+    /// Determines if bindings for every header of the target platform are committed to the
+    /// `bindings` directory of this crate.
+    ///
+    /// Otherwise, they must be generated even on *docs.rs*.
+    pub fn has_committed() -> bool {
+        let committed_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("bindings");
+        println!("cargo:rerun-if-changed={}", committed_dir.display());
+
+        committed_suffixes().iter().all(|suffix| committed_path(suffix).is_file())
+    }
+
+    /// Returns the filename suffixes of the committed bindings for the target platform.
+    fn committed_suffixes() -> Vec<String> {
+        iter::once("-info".to_string())
+            .chain(headers().into_iter().map(|header| header.filename_suffix))
+            .collect()
+    }
+
+    /// Returns the path of the committed bindings with the given filename suffix.
+    ///
+    /// The committed bindings are named as if by `$LIBUI_NG_SYS_EMIT_BINDINGS_TO`.
+    fn committed_path(suffix: &str) -> PathBuf {
+        Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("bindings")
+            .join(format!("{}{}.rs", DEFAULT_FILENAME_PREFIX, suffix))
+    }
+
+    /// Returns the filename prefix of the generated bindings and the stem derived from it,
+    /// informing `lib.rs` of the latter.
+    fn filename_stem() -> (String, String) {
+        let prefix = config::var("LIBUI_NG_SYS_BINDINGS_PREFIX")
+            .unwrap_or_else(|| DEFAULT_FILENAME_PREFIX.to_string());
+        // Generated filenames are keyed to the target triple so that bindings for one target can
+        // never be mistaken for those of another, even if an output directory is shared.
+        let stem = format!("{}-{}", prefix, env::var("TARGET").unwrap());
+        // `lib.rs` reads this to locate the generated files.
+        println!("cargo:rustc-env=LIBUI_NG_SYS_BINDINGS_STEM={}", stem);
+
+        (prefix, stem)
+    }
+
+    /// Returns the headers for which bindings are generated on the target platform.
+    fn headers() -> Vec<Header> {
+        let mut headers = vec![Header::main(), Header::control_sigs()];
+        // Headless builds may lack the platform's GUI headers altogether.
+        if !cfg!(feature = "no-platform-bindings") {
+            if build_cfg!(target_os = "macos") {
+                headers.push(Header::darwin());
+            }
+            if build_cfg!(target_os = "linux") {
+                headers.push(Header::unix());
+            }
+            if build_cfg!(target_os = "windows") {
+                headers.push(Header::windows());
+            }
+        }
+
+        headers
+    }
+
+    /// Copies each generated file in `renames` from `out_dir` to `to`, under its new name.
+    ///
+    /// The copies are reformatted according to `$LIBUI_NG_SYS_RUSTFMT_CONFIG`, if set.
+    fn emit(out_dir: &Path, to: &Path, renames: &[(&str, String)]) -> io::Result<()> {
+        fs::create_dir_all(to)?;
+        for (from, to_name) in renames {
+            fs::copy(out_dir.join(from), to.join(to_name))?;
+        }
+
+        if let Some(config_path) = config::var("LIBUI_NG_SYS_RUSTFMT_CONFIG") {
+            reformat(&config_path, renames.iter().map(|(_, to_name)| to.join(to_name)));
         }
 
         Ok(())
     }
 
+    /// Formats the given files with *rustfmt* using the configuration file at `config_path`.
+    ///
+    /// Only the emitted copies are reformatted, so the bindings in `$OUT_DIR` remain as bindgen
+    /// wrote them. This does nothing but warn if *rustfmt* is unavailable or fails.
+    fn reformat(config_path: &str, files: impl Iterator<Item = PathBuf>) {
+        let Some(rustfmt) = config::var("RUSTFMT")
+            .map(PathBuf::from)
+            .or_else(|| config::find_program("rustfmt"))
+        else {
+            println!("cargo:warning=rustfmt could not be found; emitted bindings are unformatted");
+            return;
+        };
+
+        let status = process::Command::new(rustfmt)
+            .arg("--edition=2021")
+            .arg("--config-path")
+            .arg(config_path)
+            .args(files)
+            .status();
+        if !status.is_ok_and(|status| status.success()) {
+            println!("cargo:warning=rustfmt failed to format the emitted bindings");
+        }
+    }
+
+    /// Writes constants describing the bindings to `$OUT_DIR/<stem>-info.rs`.
+    fn write_info(
+        out_dir: &Path,
+        stem: &str,
+        control_sigs: &[String],
+        functions: &[String],
+    ) -> Result<(), Error> {
+        let commit = libui_commit().unwrap_or_else(|| "unknown".to_string());
+        let control_sigs = control_sigs
+            .iter()
+            .map(|name| format!("    ({:?}, {}Signature),\n", name, name))
+            .collect::<String>();
+        let functions = functions
+            .iter()
+            .map(|name| format!("    {:?},\n", name))
+            .collect::<String>();
+        let contents = format!(
+            "\
+                /// The Git commit of *libui-ng* from which these bindings were generated, or\n\
+                /// `\"unknown\"` if it could not be determined.\n\
+                pub const LIBUI_NG_COMMIT: &str = {:?};\n\
+                \n\
+                /// Every control type paired with its signature, as reported by\n\
+                /// `uiControl::Signature`.\n\
+                pub const ALL_CONTROL_SIGNATURES: &[(&str, u32)] = &[\n{}];\n\
+                \n\
+                /// The name of every function in these bindings, sorted. Platform-specific\n\
+                /// functions, which are declared in the `platform` modules, are not included.\n\
+                pub const ALL_FUNCTIONS: &[&str] = &[\n{}];\n\
+            ",
+            commit,
+            control_sigs,
+            functions,
+        );
+
+        fs::write(out_dir.join(format!("{}-info.rs", stem)), contents)
+            .map_err(Error::WriteToFile)
+    }
+
+    /// Returns the names of the functions declared in `bindings`, sorted.
+    fn function_names(bindings: &str) -> Vec<String> {
+        let mut names = bindings
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("pub fn "))
+            .filter_map(|line| Some(line.split('(').next()?.trim().to_string()))
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        names
+    }
+
+    /// Reads the names of the control types, e.g., `uiButton`, for which *libui* defines
+    /// signatures.
+    fn control_sig_names(libui_dir: &Path) -> io::Result<Vec<String>> {
+        let header = fs::read_to_string(libui_dir.join("common/controlsigs.h"))?;
+        let names = header
+            .lines()
+            .filter_map(|line| {
+                let mut tokens = line.split_whitespace();
+                if tokens.next() != Some("#define") {
+                    return None;
+                }
+
+                tokens.next()?.strip_suffix("Signature").map(String::from)
+            })
+            .collect();
+
+        Ok(names)
+    }
+
+    /// Reads the commit checked out in the *libui-ng* submodule.
+    ///
+    /// This is unavailable when the crate was downloaded from *crates.io*, which does not preserve
+    /// Git metadata.
+    pub fn libui_commit() -> Option<String> {
+        let dot_git = Path::new("dep/libui-ng/.git");
+        // Within a submodule, `.git` is a file that points to the real Git directory.
+        let git_dir = if dot_git.is_file() {
+            let contents = fs::read_to_string(dot_git).ok()?;
+            let relative = contents.trim().strip_prefix("gitdir:")?.trim().to_string();
+
+            Path::new("dep/libui-ng").join(relative)
+        } else {
+            dot_git.to_path_buf()
+        };
+
+        let head_path = git_dir.join("HEAD");
+        println!("cargo:rerun-if-changed={}", head_path.display());
+        let head = fs::read_to_string(head_path).ok()?;
+        let head = head.trim();
+
+        let commit = match head.strip_prefix("ref:") {
+            Some(reference) => fs::read_to_string(git_dir.join(reference.trim())).ok()?,
+            None => head.to_string(),
+        };
+        let commit = commit.trim();
+
+        if commit.len() == 40 && commit.chars().all(|c| c.is_ascii_hexdigit()) {
+            Some(commit.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// The criterion by which previously-generated bindings are considered up-to-date.
+    enum Freshness {
+        /// The bindings are always regenerated.
+        Stale,
+        /// The bindings are up-to-date if they exist.
+        Exists,
+        /// The bindings are up-to-date if they exist and were generated from the same inputs.
+        Fingerprint,
+    }
+
     struct Header {
         include_stmts: Vec<IncludeStmt>,
-        filename: String,
+        filename_prefix: String,
+        /// The portion of the filename following [`Header::filename_prefix`].
+        filename_suffix: String,
         blocklists_main: bool,
     }
 
@@ -410,7 +2106,8 @@ mod bindings {
                         arg: "ui.h".to_string(),
                     },
                 ],
-                filename: "bindings".to_string(),
+                filename_prefix: DEFAULT_FILENAME_PREFIX.to_string(),
+                filename_suffix: String::new(),
                 blocklists_main: false,
             }
         }
@@ -423,7 +2120,8 @@ mod bindings {
                         arg: "common/controlsigs.h".to_string(),
                     },
                 ],
-                filename: "bindings-control-sigs".to_string(),
+                filename_prefix: DEFAULT_FILENAME_PREFIX.to_string(),
+                filename_suffix: "-control-sigs".to_string(),
                 blocklists_main: true,
             }
         }
@@ -456,20 +2154,80 @@ mod bindings {
                         arg: format!("ui_{}.h", name),
                     },
                 ],
-                filename: format!("bindings-{}", name),
+                filename_prefix: DEFAULT_FILENAME_PREFIX.to_string(),
+                filename_suffix: format!("-{}", name),
                 blocklists_main: true,
             }
         }
 
-        fn generate(self, libui_dir: &Path, out_dir: &Path) -> Result<(), Error> {
+        fn with_filename_prefix(self, prefix: impl Into<String>) -> Self {
+            Self {
+                filename_prefix: prefix.into(),
+                ..self
+            }
+        }
+
+        /// Returns the filename of the generated bindings without the `.rs` extension.
+        fn name(&self) -> String {
+            format!("{}{}", self.filename_prefix, self.filename_suffix)
+        }
+
+        fn filename(&self) -> String {
+            format!("{}.rs", self.name())
+        }
+
+        fn fingerprint_filename(&self) -> String {
+            format!("{}.fingerprint", self.name())
+        }
+
+        /// Returns the filename of the Make-style depfile listing the headers that were included
+        /// when the bindings were last generated.
+        fn depfile_filename(&self) -> String {
+            format!("{}.d", self.name())
+        }
+
+        fn generate(
+            self,
+            libui_dir: &Path,
+            out_dir: &Path,
+            clang_args: &[String],
+            freshness: Freshness,
+        ) -> Result<(), Error> {
             static LIBUI_REGEX: &str = "ui(?:[A-Z][a-z0-9]*)*";
 
+            let contents = self.contents(libui_dir);
+            let depfile_path = out_dir.join(self.depfile_filename());
+            let deps = depfile::read(&depfile_path);
+            let fingerprint = self.fingerprint(libui_dir, &contents, clang_args, &deps);
+            let fingerprint_path = out_dir.join(self.fingerprint_filename());
+
+            let exists = out_dir.join(self.filename()).exists();
+            let is_fresh = match freshness {
+                Freshness::Stale => false,
+                Freshness::Exists => exists,
+                Freshness::Fingerprint => {
+                    exists
+                        && fs::read_to_string(&fingerprint_path)
+                            .is_ok_and(|previous| previous == fingerprint)
+                }
+            };
+            if is_fresh {
+                // Cargo only remembers the paths printed by the latest run of the build script.
+                track_deps(&deps);
+                return Ok(());
+            }
+
+            let includes = Arc::new(Mutex::new(Vec::new()));
             let mut builder = bindgen::builder()
-                .header_contents("wrapper.h", &self.contents(libui_dir))
-                .parse_callbacks(Box::new(bindgen::CargoCallbacks))
+                .header_contents("wrapper.h", &contents)
+                .parse_callbacks(Box::new(Callbacks { includes: Arc::clone(&includes) }))
                 .allowlist_function(LIBUI_REGEX)
                 .allowlist_type(LIBUI_REGEX)
                 .allowlist_var(LIBUI_REGEX)
+                // *libui* declares its enums as `unsigned int` typedefs followed by anonymous
+                // enums, so they are generated as plain integer constants. There are no Rust enums
+                // whose matches a new variant could break, and thus nothing to mark
+                // `#[non_exhaustive]`.
                 .blocklist_item("_bindgen.*");
 
             // Note: Virtually every wrapper except that for "ui.h" should blocklist "ui.h".
@@ -477,13 +2235,137 @@ mod bindings {
                 builder = builder.blocklist_file(".*ui\\.h");
             }
 
-            builder
-                .clang_args(ClangArgs::new().as_args())
+            // Vendored bindings should be byte-identical whenever they are regenerated from the
+            // same inputs, so we omit the bindgen version, which may differ between patch
+            // releases, and format with the default style rather than any `rustfmt.toml` in scope.
+            let bindings = builder
+                .clang_args(clang_args)
                 .layout_tests(false)
+                .disable_header_comment()
+                .rustfmt_bindings(true)
+                .rustfmt_configuration_file(None)
                 .generate()
-                .map_err(|_| Error::Generate)?
-                .write_to_file(out_dir.join(format!("{}.rs", self.filename)))
-                .map_err(Error::WriteToFile)
+                .map_err(|_| Error::Generate)?;
+            let mut bindings = bindings.to_string();
+            if cfg!(feature = "strict-bindings") {
+                self.check_functions(libui_dir, &bindings)?;
+            }
+            // bindgen can't attach `#[link]` attributes to the `extern` blocks it generates, but a
+            // single attribute links *libui* for the whole crate, so it goes on an empty block of
+            // its own in the main bindings.
+            if cfg!(feature = "link-attrs") && !self.blocklists_main {
+                bindings.push_str(&format!(
+                    "\n#[link(name = \"ui\", kind = \"{}\")]\nextern \"C\" {{}}\n",
+                    crate::link_kind(),
+                ));
+            }
+            if cfg!(feature = "owned-text") {
+                // Every *libui* function returning a mutable string, such as `uiWindowTitle`,
+                // allocates it for the caller to free with `uiFreeText`; the borrowed strings it
+                // returns, such as that of `uiAttributedStringString`, are `const`.
+                bindings = bindings.replace("-> *mut ::std::os::raw::c_char;", "-> UiOwnedText;");
+            }
+            fs::write(out_dir.join(self.filename()), bindings).map_err(Error::WriteToFile)?;
+
+            let mut deps = includes
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|path| source_path(libui_dir, path))
+                .collect::<Vec<_>>();
+            deps.sort();
+            deps.dedup();
+            track_deps(&deps);
+            depfile::write(&depfile_path, &out_dir.join(self.filename()), &deps)
+                .map_err(Error::WriteToFile)?;
+
+            // The included headers may have changed since the fingerprint was last computed.
+            let fingerprint = self.fingerprint(libui_dir, &contents, clang_args, &deps);
+            fs::write(fingerprint_path, fingerprint).map_err(Error::WriteToFile)
+        }
+
+        /// Checks that every function declared with `_UI_EXTERN` in this header's local includes
+        /// made it into `bindings`.
+        ///
+        /// bindgen only logs a warning when it fails to parse a declaration, which is easily
+        /// missed in build output; this turns such omissions into errors.
+        fn check_functions(&self, libui_dir: &Path, bindings: &str) -> Result<(), Error> {
+            let mut missing = Vec::new();
+            for stmt in &self.include_stmts {
+                if !matches!(stmt.kind, IncludeStmtKind::Local) {
+                    continue;
+                }
+                // Items from "ui.h" are deliberately left out of every other wrapper.
+                if self.blocklists_main && stmt.arg == "ui.h" {
+                    continue;
+                }
+
+                let header = fs::read_to_string(libui_dir.join(&stmt.arg))
+                    .map_err(Error::ReadHeader)?;
+                let names = header
+                    .lines()
+                    .filter(|line| line.trim_start().starts_with("_UI_EXTERN"))
+                    .filter_map(|line| line.split('(').next()?.split_whitespace().last())
+                    .map(|name| name.trim_start_matches('*'));
+                for name in names {
+                    if !bindings.contains(&format!("fn {}(", name)) {
+                        missing.push(name.to_string());
+                    }
+                }
+            }
+
+            if missing.is_empty() {
+                Ok(())
+            } else {
+                Err(Error::MissingFunctions(missing))
+            }
+        }
+
+        /// Computes a digest of the inputs to bindgen.
+        ///
+        /// Headers included by the local headers are considered through `deps`, as read from the
+        /// depfile.
+        fn fingerprint(
+            &self,
+            libui_dir: &Path,
+            contents: &str,
+            clang_args: &[String],
+            deps: &[PathBuf],
+        ) -> String {
+            let mut hasher = DefaultHasher::new();
+            contents.hash(&mut hasher);
+            clang_args.hash(&mut hasher);
+            self.blocklists_main.hash(&mut hasher);
+            // The `serde` feature changes which derives are generated.
+            cfg!(feature = "serde").hash(&mut hasher);
+            // As do `owned-text` and `link-attrs`, the latter of which also depends on the kind of
+            // link.
+            cfg!(feature = "owned-text").hash(&mut hasher);
+            cfg!(feature = "link-attrs").hash(&mut hasher);
+            crate::link_kind().hash(&mut hasher);
+            for stmt in &self.include_stmts {
+                if let IncludeStmtKind::Local = stmt.kind {
+                    fs::read(libui_dir.join(&stmt.arg)).ok().hash(&mut hasher);
+                }
+            }
+            for dep in deps {
+                dep.hash(&mut hasher);
+                fs::read(dep).ok().hash(&mut hasher);
+            }
+
+            format!("{:016x}", hasher.finish())
+        }
+
+        fn write_stub(self, out_dir: &Path) -> Result<(), Error> {
+            static STUB: &str = "\
+                // These stub bindings were written in place of real bindings because\n\
+                // `$LIBUI_NG_SYS_STUB_BINDINGS` was set.\n\
+            ";
+
+            // The stubs must not be mistaken for up-to-date bindings later.
+            let _ = fs::remove_file(out_dir.join(self.fingerprint_filename()));
+
+            fs::write(out_dir.join(self.filename()), STUB).map_err(Error::WriteToFile)
         }
 
         fn contents(&self, libui_dir: &Path) -> String {
@@ -496,6 +2378,53 @@ mod bindings {
         }
     }
 
+    /// The structs that are plain data---i.e., contain no pointers---and may therefore derive
+    /// `serde::Serialize` and `serde::Deserialize` with the `serde` feature.
+    static SERDE_STRUCTS: &[&str] = &[
+        "uiAreaKeyEvent",
+        "uiAreaMouseEvent",
+        "uiDrawBrushGradientStop",
+        "uiDrawMatrix",
+        "uiInitOptions",
+        "uiTableTextColumnOptionalParams",
+    ];
+
+    /// Maps a header included from the copy of *libui* in `$OUT_DIR` back to the vendored
+    /// original, which is what may actually be edited; other paths are returned as-is.
+    fn source_path(libui_dir: &Path, path: &Path) -> PathBuf {
+        match path.strip_prefix(libui_dir) {
+            Ok(relative) => Path::new("dep/libui-ng").join(relative),
+            Err(_) => path.to_path_buf(),
+        }
+    }
+
+    /// Tells Cargo to rerun the build script whenever any of `deps` changes.
+    fn track_deps(deps: &[PathBuf]) {
+        for dep in deps {
+            println!("cargo:rerun-if-changed={}", dep.display());
+        }
+    }
+
+    #[derive(Debug)]
+    struct Callbacks {
+        /// Every header that Clang included, in order of inclusion.
+        includes: Arc<Mutex<Vec<PathBuf>>>,
+    }
+
+    impl bindgen::callbacks::ParseCallbacks for Callbacks {
+        fn include_file(&self, filename: &str) {
+            self.includes.lock().unwrap().push(PathBuf::from(filename));
+        }
+
+        fn add_derives(&self, name: &str) -> Vec<String> {
+            if cfg!(feature = "serde") && SERDE_STRUCTS.contains(&name) {
+                vec!["serde::Serialize".to_string(), "serde::Deserialize".to_string()]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
     struct IncludeStmt {
         kind: IncludeStmtKind,
         arg: String,
@@ -533,7 +2462,13 @@ mod bindings {
 
     impl ClangArgs {
         fn new() -> Self {
-            if build_cfg!(target_os = "macos") {
+            if cfg!(feature = "no-platform-bindings") {
+                // Only the platform-specific headers need the platform's GUI toolkit.
+                Self {
+                    defines: Vec::new(),
+                    include_paths: Vec::new(),
+                }
+            } else if build_cfg!(target_os = "macos") {
                 Self::new_macos()
             } else if build_cfg!(target_os = "linux") {
                 Self::new_linux()
@@ -552,20 +2487,19 @@ mod bindings {
         }
 
         fn new_linux() -> Self {
-            let gtk = pkg_config::Config::new()
-                .atleast_version("3.10.0")
-                .print_system_cflags(true)
-                .print_system_libs(true)
-                .probe("gtk+-3.0")
-                .unwrap();
+            // `import_dylibs` has already told Cargo to link against GTK.
+            let gtk = gtk::probe();
 
-            let defines = gtk
+            let mut defines = gtk
                 .defines
                 .into_iter()
                 .map(|(key, value)| {
                     ClangDefine { key, value }
                 })
-                .collect();
+                .collect::<Vec<_>>();
+            // pkg-config reports these in no particular order, which would otherwise change the
+            // fingerprint from run to run.
+            defines.sort_by(|a, b| a.key.cmp(&b.key));
 
             let include_paths = gtk
                 .include_paths
@@ -611,7 +2545,36 @@ mod bindings {
                     ]
                 });
 
-            defines.chain(includes).collect()
+            // *bindgen* already passes the Cargo target triple to Clang, but translates only the
+            // names of Apple's ARM targets, so Clang would reject, e.g., RISC-V triples. An
+            // explicit `--target` replaces the one *bindgen* would add.
+            let target = Self::clang_target().map(|triple| format!("--target={}", triple));
+            // Availability annotations in the system headers depend on the deployment target.
+            let min_version = macos::deployment_target()
+                .map(|version| format!("-mmacosx-version-min={}", version));
+
+            defines.chain(includes).chain(target).chain(min_version).collect()
+        }
+
+        /// Translates the Cargo target triple into one that Clang understands, if it differs from
+        /// the host triple.
+        ///
+        /// When building for the host, the triple that *bindgen* passes is left alone.
+        fn clang_target() -> Option<String> {
+            let target = env::var("TARGET").ok()?;
+            if env::var("HOST").ok()? == target {
+                return None;
+            }
+
+            // Rust names RISC-V architectures after their extensions, e.g., "riscv64gc", whereas
+            // Clang expects the base architecture.
+            let (arch, rest) = target.split_once('-')?;
+            let arch = if arch.starts_with("riscv") { &arch[.."riscv64".len()] } else { arch };
+            // As *bindgen* does, since Clang names 64-bit ARM "arm64" on Apple platforms.
+            let arch = if arch == "aarch64" && rest.starts_with("apple-") { "arm64" } else { arch };
+
+            Some(format!("{}-{}", arch, rest))
         }
     }
+
 }
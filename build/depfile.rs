@@ -0,0 +1,97 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Make-style depfiles recording the headers that bindings were generated from.
+//!
+//! This is included by both the build script and `tests/build.rs`, as Cargo never tests build
+//! scripts themselves.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Writes a Make-style depfile stating that `target` depends on `deps`, one per line.
+pub fn write(path: &Path, target: &Path, deps: &[PathBuf]) -> io::Result<()> {
+    let escape = |path: &Path| path.display().to_string().replace(' ', "\\ ");
+
+    let mut contents = format!("{}:", escape(target));
+    for dep in deps {
+        contents.push_str(" \\\n    ");
+        contents.push_str(&escape(dep));
+    }
+    contents.push('\n');
+
+    fs::write(path, contents)
+}
+
+/// Reads the dependencies from a depfile written by [`write`].
+///
+/// Returns nothing if the depfile doesn't exist, e.g., before bindings are first generated.
+pub fn read(path: &Path) -> Vec<PathBuf> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .skip(1)
+        .map(|line| line.trim().trim_end_matches('\\').trim_end().replace("\\ ", " "))
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    /// Returns a path in the temporary directory unique to this process and test.
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("libui-ng-sys-{}-{}", process::id(), name))
+    }
+
+    #[test]
+    fn depfile_round_trips() {
+        let path = temp_path("round-trip.d");
+        let deps = vec![
+            PathBuf::from("dep/libui-ng/ui.h"),
+            PathBuf::from("/usr/include/gtk-3.0/gtk/gtk.h"),
+            PathBuf::from("/Library/Application Support/a b.h"),
+        ];
+        write(&path, Path::new("out dir/bindings.rs"), &deps).unwrap();
+        let read = read(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read, deps);
+    }
+
+    #[test]
+    fn depfile_escapes_spaces() {
+        let path = temp_path("escape.d");
+        write(&path, Path::new("a b.rs"), &[PathBuf::from("c d.h")]).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents, "a\\ b.rs: \\\n    c\\ d.h\n");
+    }
+
+    #[test]
+    fn depfile_without_deps_round_trips() {
+        let path = temp_path("empty.d");
+        write(&path, Path::new("bindings.rs"), &[]).unwrap();
+        let read = read(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(read.is_empty());
+    }
+
+    #[test]
+    fn missing_depfile_has_no_deps() {
+        assert!(read(&temp_path("missing.d")).is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! The link directives printed by the build script.
+
+use std::path::PathBuf;
+
+/// The libraries to link and where to find them, collected over the run of the build script
+/// and then emitted at once with [`LinkSpec::emit`].
+#[derive(Default)]
+pub struct LinkSpec {
+    search_dirs: Vec<PathBuf>,
+    /// The kind and name of each library, in order of linking.
+    libs: Vec<(&'static str, String)>,
+    args: Vec<String>,
+}
+
+impl LinkSpec {
+    /// Adds a directory in which to search for libraries.
+    pub fn search(&mut self, dir: impl Into<PathBuf>) {
+        self.search_dirs.push(dir.into());
+    }
+
+    /// Adds a library of the given kind, e.g., `"static"` or `"dylib"`.
+    ///
+    /// Libraries must be added after those that depend on them.
+    pub fn lib(&mut self, kind: &'static str, name: impl Into<String>) {
+        self.libs.push((kind, name.into()));
+    }
+
+    /// Adds an argument to pass to the linker.
+    pub fn arg(&mut self, arg: impl Into<String>) {
+        self.args.push(arg.into());
+    }
+
+    /// Tells Cargo about everything added so far.
+    ///
+    /// Duplicates are emitted once. A library added more than once is linked where it was
+    /// last added, which keeps static libraries after everything that depends on them.
+    pub fn emit(&self) {
+        for directive in self.directives() {
+            println!("{}", directive);
+        }
+    }
+
+    /// Returns the directives printed by [`LinkSpec::emit`], in order.
+    fn directives(&self) -> Vec<String> {
+        let mut directives = Vec::new();
+        for dir in dedup(self.search_dirs.iter().map(PathBuf::as_path)) {
+            directives.push(format!("cargo:rustc-link-search={}", dir.display()));
+        }
+
+        let mut libs = self.libs.iter().rev().collect::<Vec<_>>();
+        let mut seen = Vec::new();
+        libs.retain(|(_, name)| {
+            let is_new = !seen.contains(&name);
+            seen.push(name);
+            is_new
+        });
+        for (kind, name) in libs.into_iter().rev() {
+            directives.push(format!("cargo:rustc-link-lib={}={}", kind, name));
+        }
+
+        for arg in dedup(self.args.iter().map(String::as_str)) {
+            directives.push(format!("cargo:rustc-link-arg={}", arg));
+        }
+
+        directives
+    }
+}
+
+/// Returns the distinct items of `items` in order of first appearance.
+fn dedup<'a, T: ?Sized + PartialEq>(items: impl Iterator<Item = &'a T>) -> Vec<&'a T> {
+    let mut distinct = Vec::new();
+    for item in items {
+        if !distinct.contains(&item) {
+            distinct.push(item);
+        }
+    }
+
+    distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedup_keeps_first_occurrences() {
+        let items = ["b", "a", "b", "c", "a"];
+        assert_eq!(dedup(items.iter().copied()), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn duplicate_libs_are_linked_where_last_added() {
+        let mut spec = LinkSpec::default();
+        spec.lib("static", "ui");
+        spec.lib("dylib", "gtk-3");
+        spec.lib("dylib", "glib-2.0");
+        spec.lib("dylib", "gtk-3");
+        spec.lib("static", "ui");
+
+        assert_eq!(
+            spec.directives(),
+            [
+                "cargo:rustc-link-lib=dylib=glib-2.0",
+                "cargo:rustc-link-lib=dylib=gtk-3",
+                "cargo:rustc-link-lib=static=ui",
+            ],
+        );
+    }
+
+    #[test]
+    fn duplicate_search_dirs_and_args_are_emitted_once() {
+        let mut spec = LinkSpec::default();
+        spec.search("/usr/lib");
+        spec.search("/opt/lib");
+        spec.search("/usr/lib");
+        spec.arg("-Wl,--as-needed");
+        spec.arg("-Wl,--as-needed");
+
+        assert_eq!(
+            spec.directives(),
+            [
+                "cargo:rustc-link-search=/usr/lib",
+                "cargo:rustc-link-search=/opt/lib",
+                "cargo:rustc-link-arg=-Wl,--as-needed",
+            ],
+        );
+    }
+}
@@ -0,0 +1,42 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version numbers reported by build tools, such as `meson --version`.
+
+/// Parses a version string such as `1.2.3` into its numeric components.
+///
+/// The components compare as the version numbers do.
+pub fn parse(version: &str) -> Option<Vec<u32>> {
+    version.trim().split('.').map(|part| part.parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(parse("0.58.0"), Some(vec![0, 58, 0]));
+        // `meson --version` ends its output with a newline.
+        assert_eq!(parse("1.4.1\n"), Some(vec![1, 4, 1]));
+        assert_eq!(parse("2"), Some(vec![2]));
+    }
+
+    #[test]
+    fn rejects_malformed_versions() {
+        for version in ["", "1..0", "1.2.rc1", "v1.2.3", "1.2.3-dev"] {
+            assert_eq!(parse(version), None, "{:?}", version);
+        }
+    }
+
+    #[test]
+    fn compares_versions_numerically() {
+        let min = parse("0.58.0");
+        assert!(parse("0.58.0") >= min);
+        assert!(parse("0.60.1") >= min);
+        assert!(parse("1.0.0") >= min);
+        assert!(parse("0.57.2") < min);
+        assert!(parse("0.9.0") < min);
+    }
+}
@@ -1,11 +1,11 @@
 #![windows_subsystem = "windows"]
 
 use libui_ng_sys::*;
-use std::{ffi, os::raw::c_void, ptr};
+use std::{ffi, mem, os::raw::c_void, ptr};
 
 fn main() {
     unsafe {
-        let mut options = uiInitOptions { Size: 0 };
+        let mut options = uiInitOptions { Size: mem::size_of::<uiInitOptions>() as _ };
         uiInit(ptr::addr_of_mut!(options));
 
         let file_menu_name = ffi::CString::new("File").unwrap();
@@ -0,0 +1,123 @@
+#![windows_subsystem = "windows"]
+
+use libui_ng_sys::*;
+use std::{ffi, mem, os::raw::c_void, ptr};
+
+/// An area handler followed by the state its callbacks operate on.
+///
+/// *libui* passes the handler pointer to every callback, so placing [`uiAreaHandler`] first lets
+/// the callbacks cast it back to the full struct.
+#[repr(C)]
+struct Handler {
+    raw: uiAreaHandler,
+    /// The last point clicked, at which the square is drawn.
+    center: (f64, f64),
+}
+
+const SQUARE_SIZE: f64 = 40.0;
+
+fn main() {
+    unsafe {
+        let mut options = uiInitOptions { Size: mem::size_of::<uiInitOptions>() as _ };
+        uiInit(ptr::addr_of_mut!(options));
+
+        let window_name = ffi::CString::new("libui-ng-sys").unwrap();
+        let window = uiNewWindow(window_name.as_ptr(), 320, 240, 0);
+        uiWindowSetMargined(window, 1);
+        uiWindowOnClosing(window, Some(window_on_closing), ptr::null_mut());
+
+        // The handler must outlive the area, which lives until the window is destroyed.
+        let handler = Box::leak(Box::new(Handler {
+            raw: uiAreaHandler {
+                Draw: Some(handler_draw),
+                MouseEvent: Some(handler_mouse_event),
+                MouseCrossed: Some(handler_mouse_crossed),
+                DragBroken: Some(handler_drag_broken),
+                KeyEvent: Some(handler_key_event),
+            },
+            center: (160.0, 120.0),
+        }));
+        let area = uiNewArea(ptr::addr_of_mut!(handler.raw));
+        uiWindowSetChild(window, area.cast());
+
+        uiControlShow(window.cast());
+        uiMain();
+        uiUninit();
+    }
+}
+
+unsafe extern "C" fn window_on_closing(window: *mut uiWindow, _: *mut c_void) -> i32 {
+    uiControlDestroy(window.cast());
+    uiQuit();
+    0
+}
+
+unsafe extern "C" fn handler_draw(
+    handler: *mut uiAreaHandler,
+    _: *mut uiArea,
+    params: *mut uiAreaDrawParams,
+) {
+    let handler = &*handler.cast::<Handler>();
+    let params = &*params;
+
+    let mut brush: uiDrawBrush = mem::zeroed();
+    brush.Type = uiDrawBrushTypeSolid as _;
+
+    // Paint the background white.
+    let background = uiDrawNewPath(uiDrawFillModeWinding as _);
+    uiDrawPathAddRectangle(background, 0.0, 0.0, params.AreaWidth, params.AreaHeight);
+    uiDrawPathEnd(background);
+    brush.R = 1.0;
+    brush.G = 1.0;
+    brush.B = 1.0;
+    brush.A = 1.0;
+    uiDrawFill(params.Context, background, &mut brush);
+    uiDrawFreePath(background);
+
+    // Paint a blue square centered on the last click.
+    let (x, y) = handler.center;
+    let square = uiDrawNewPath(uiDrawFillModeWinding as _);
+    uiDrawPathAddRectangle(
+        square,
+        x - SQUARE_SIZE / 2.0,
+        y - SQUARE_SIZE / 2.0,
+        SQUARE_SIZE,
+        SQUARE_SIZE,
+    );
+    uiDrawPathEnd(square);
+    brush.R = 0.2;
+    brush.G = 0.4;
+    brush.B = 0.8;
+    uiDrawFill(params.Context, square, &mut brush);
+    uiDrawFreePath(square);
+}
+
+unsafe extern "C" fn handler_mouse_event(
+    handler: *mut uiAreaHandler,
+    area: *mut uiArea,
+    event: *mut uiAreaMouseEvent,
+) {
+    let handler = &mut *handler.cast::<Handler>();
+    let event = &*event;
+
+    // Move the square on a left click.
+    if event.Down == 1 {
+        handler.center = (event.X, event.Y);
+        uiAreaQueueRedrawAll(area);
+    }
+}
+
+// *libui* calls every handler callback unconditionally, so even unused ones must be set.
+
+unsafe extern "C" fn handler_mouse_crossed(_: *mut uiAreaHandler, _: *mut uiArea, _: i32) {}
+
+unsafe extern "C" fn handler_drag_broken(_: *mut uiAreaHandler, _: *mut uiArea) {}
+
+unsafe extern "C" fn handler_key_event(
+    _: *mut uiAreaHandler,
+    _: *mut uiArea,
+    _: *mut uiAreaKeyEvent,
+) -> i32 {
+    // Let the key propagate.
+    0
+}
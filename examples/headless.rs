@@ -0,0 +1,23 @@
+//! Initializes and uninitializes *libui-ng* without opening any windows.
+//!
+//! This verifies that *libui-ng* links and initializes, e.g., in CI under a virtual display such
+//! as Xvfb. The process exits with a nonzero status if initialization fails.
+
+use libui_ng_sys::*;
+use std::{ffi::CStr, mem, process, ptr};
+
+fn main() {
+    unsafe {
+        let mut options = uiInitOptions { Size: mem::size_of::<uiInitOptions>() as _ };
+        let err = uiInit(ptr::addr_of_mut!(options));
+        if !err.is_null() {
+            eprintln!("uiInit() failed: {}", CStr::from_ptr(err).to_string_lossy());
+            uiFreeInitError(err);
+            process::exit(1);
+        }
+
+        uiUninit();
+    }
+
+    println!("libui-ng initialized and uninitialized successfully");
+}
@@ -0,0 +1,222 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Decoders for the events passed to area handlers.
+
+use std::ops::{BitAnd, BitOr};
+
+use crate::*;
+
+/// A set of modifier keys, wrapping [`uiModifiers`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Modifiers(pub uiModifiers);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const CTRL: Self = Self(uiModifierCtrl as uiModifiers);
+    pub const ALT: Self = Self(uiModifierAlt as uiModifiers);
+    pub const SHIFT: Self = Self(uiModifierShift as uiModifiers);
+    pub const SUPER: Self = Self(uiModifierSuper as uiModifiers);
+
+    /// Determines if every modifier in `other` is also in this set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Modifiers {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+/// The set of mouse buttons held during a mouse event.
+///
+/// Button `n` (numbered from 1) corresponds to bit `n - 1`, as in
+/// [`uiAreaMouseEvent::Held1To64`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MouseButtons(pub u64);
+
+impl MouseButtons {
+    /// Determines if the given button, numbered from 1, is held.
+    ///
+    /// Buttons outside `1..=64` are never held.
+    pub fn is_held(self, button: u32) -> bool {
+        (1..=64).contains(&button) && self.0 & (1 << (button - 1)) != 0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// An owned copy of a [`uiAreaMouseEvent`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MouseEvent {
+    pub x: f64,
+    pub y: f64,
+    pub area_width: f64,
+    pub area_height: f64,
+    /// The button that was pressed, if any.
+    pub down: Option<u32>,
+    /// The button that was released, if any.
+    pub up: Option<u32>,
+    /// The click count, e.g., 2 for a double click.
+    pub count: u32,
+    pub modifiers: Modifiers,
+    pub held: MouseButtons,
+}
+
+impl MouseEvent {
+    /// Decodes the event pointed to by `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid [`uiAreaMouseEvent`], such as that passed to
+    /// [`uiAreaHandler::MouseEvent`].
+    pub unsafe fn from_raw(ptr: *const uiAreaMouseEvent) -> Self {
+        check_thread!();
+        let raw = &*ptr;
+        let button = |it: std::os::raw::c_int| u32::try_from(it).ok().filter(|it| *it != 0);
+
+        Self {
+            x: raw.X,
+            y: raw.Y,
+            area_width: raw.AreaWidth,
+            area_height: raw.AreaHeight,
+            down: button(raw.Down),
+            up: button(raw.Up),
+            count: u32::try_from(raw.Count).unwrap_or(0),
+            modifiers: Modifiers(raw.Modifiers),
+            held: MouseButtons(raw.Held1To64),
+        }
+    }
+}
+
+/// A key without a character representation, corresponding to [`uiExtKey`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ExtKey {
+    Escape,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Up,
+    Down,
+    Left,
+    Right,
+    /// A function key, numbered from 1 to 12.
+    F(u8),
+    /// A digit on the numeric keypad.
+    Numpad(u8),
+    NumpadDot,
+    NumpadEnter,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+}
+
+impl ExtKey {
+    /// Converts a raw [`uiExtKey`], returning `None` if it is unrecognized.
+    pub fn from_raw(raw: uiExtKey) -> Option<Self> {
+        let key = match raw {
+            uiExtKeyEscape => Self::Escape,
+            uiExtKeyInsert => Self::Insert,
+            uiExtKeyDelete => Self::Delete,
+            uiExtKeyHome => Self::Home,
+            uiExtKeyEnd => Self::End,
+            uiExtKeyPageUp => Self::PageUp,
+            uiExtKeyPageDown => Self::PageDown,
+            uiExtKeyUp => Self::Up,
+            uiExtKeyDown => Self::Down,
+            uiExtKeyLeft => Self::Left,
+            uiExtKeyRight => Self::Right,
+            uiExtKeyNDot => Self::NumpadDot,
+            uiExtKeyNEnter => Self::NumpadEnter,
+            uiExtKeyNAdd => Self::NumpadAdd,
+            uiExtKeyNSubtract => Self::NumpadSubtract,
+            uiExtKeyNMultiply => Self::NumpadMultiply,
+            uiExtKeyNDivide => Self::NumpadDivide,
+            // The function and keypad digit keys are numbered contiguously.
+            raw if (uiExtKeyF1..=uiExtKeyF12).contains(&raw) => {
+                Self::F((raw - uiExtKeyF1 + 1) as u8)
+            }
+            raw if (uiExtKeyN0..=uiExtKeyN9).contains(&raw) => {
+                Self::Numpad((raw - uiExtKeyN0) as u8)
+            }
+            _ => return None,
+        };
+
+        Some(key)
+    }
+}
+
+/// The key involved in a [`KeyEvent`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Key {
+    /// A key with a character representation.
+    Char(char),
+    Ext(ExtKey),
+    /// A modifier key on its own.
+    Modifier(Modifiers),
+    /// A key that *libui* reported but that could not be decoded.
+    Unknown,
+}
+
+/// An owned copy of a [`uiAreaKeyEvent`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyEvent {
+    pub key: Key,
+    /// The modifiers held during the event.
+    pub modifiers: Modifiers,
+    /// Whether the key was released rather than pressed.
+    pub is_up: bool,
+}
+
+impl KeyEvent {
+    /// Decodes the event pointed to by `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid [`uiAreaKeyEvent`], such as that passed to
+    /// [`uiAreaHandler::KeyEvent`].
+    pub unsafe fn from_raw(ptr: *const uiAreaKeyEvent) -> Self {
+        check_thread!();
+        let raw = &*ptr;
+
+        // *libui* fills in exactly one of these fields.
+        let key = if raw.Key != 0 {
+            Key::Char(raw.Key as u8 as char)
+        } else if raw.ExtKey != 0 {
+            ExtKey::from_raw(raw.ExtKey).map_or(Key::Unknown, Key::Ext)
+        } else if raw.Modifier != 0 {
+            Key::Modifier(Modifiers(raw.Modifier))
+        } else {
+            Key::Unknown
+        };
+
+        Self {
+            key,
+            modifiers: Modifiers(raw.Modifiers),
+            is_up: raw.Up != 0,
+        }
+    }
+}
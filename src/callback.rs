@@ -0,0 +1,215 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Closure-based event handlers.
+//!
+//! *libui* callbacks receive an untyped context pointer. The functions in this module box a Rust
+//! closure, pass it to *libui* as that context, and register a trampoline that calls the closure.
+//!
+//! *libui* never reports when a callback is unregistered, so each closure lives for the remainder
+//! of the program, even if it is later replaced by another.
+
+use std::{
+    ffi::CString,
+    os::raw::{c_int, c_void},
+};
+
+use crate::{
+    control::{new_control, Checkbox, RadioButtons, Slider, Window},
+    *,
+};
+
+/// Moves `f` to the heap and returns a context pointer to it.
+///
+/// The closure is never freed.
+pub(crate) fn into_data<F>(f: F) -> *mut c_void {
+    Box::into_raw(Box::new(f)).cast()
+}
+
+/// Recovers the closure from a context pointer returned by [`into_data`].
+///
+/// # Safety
+///
+/// `data` must have been returned by `into_data::<F>`.
+pub(crate) unsafe fn from_data<'a, F>(data: *mut c_void) -> &'a mut F {
+    &mut *data.cast::<F>()
+}
+
+/// Registers a closure to be called when the user asks to quit, e.g., with the quit menu item.
+///
+/// *libui* quits if the closure returns `true`; it is then the closure's responsibility to destroy
+/// any windows. This wraps [`uiOnShouldQuit`].
+pub fn on_should_quit<F: FnMut() -> bool + 'static>(f: F) {
+    check_thread!();
+    unsafe extern "C" fn trampoline<F: FnMut() -> bool>(data: *mut c_void) -> c_int {
+        c_int::from(from_data::<F>(data)())
+    }
+
+    unsafe { uiOnShouldQuit(Some(trampoline::<F>), into_data(f)) };
+}
+
+impl Window {
+    /// Registers a closure to be called with the new content width and height whenever this
+    /// window is resized.
+    ///
+    /// This wraps [`uiWindowOnContentSizeChanged`].
+    pub fn on_resize<F: FnMut(i32, i32) + 'static>(&mut self, f: F) {
+        check_thread!();
+        unsafe extern "C" fn trampoline<F: FnMut(i32, i32)>(
+            window: *mut uiWindow,
+            data: *mut c_void,
+        ) {
+            let (mut width, mut height): (c_int, c_int) = (0, 0);
+            uiWindowContentSize(window, &mut width, &mut height);
+            from_data::<F>(data)(width, height);
+        }
+
+        unsafe { uiWindowOnContentSizeChanged(self.as_ptr(), Some(trampoline::<F>), into_data(f)) };
+    }
+}
+
+impl Slider {
+    /// Creates a slider whose value ranges from `min` to `max`, inclusive.
+    ///
+    /// This wraps [`uiNewSlider`].
+    pub fn new(min: i32, max: i32) -> Self {
+        new_control!(uiNewSlider(min, max))
+    }
+
+    /// Returns the current value.
+    ///
+    /// This wraps [`uiSliderValue`].
+    pub fn value(&self) -> i32 {
+        check_thread!();
+        unsafe { uiSliderValue(self.as_ptr()) }
+    }
+
+    /// Sets the current value.
+    ///
+    /// This wraps [`uiSliderSetValue`].
+    pub fn set_value(&mut self, value: i32) {
+        check_thread!();
+        unsafe { uiSliderSetValue(self.as_ptr(), value) };
+    }
+
+    /// Registers a closure to be called with the new value whenever the user moves this slider.
+    ///
+    /// This wraps [`uiSliderOnChanged`].
+    pub fn on_changed<F: FnMut(i32) + 'static>(&mut self, f: F) {
+        check_thread!();
+        unsafe extern "C" fn trampoline<F: FnMut(i32)>(slider: *mut uiSlider, data: *mut c_void) {
+            from_data::<F>(data)(uiSliderValue(slider));
+        }
+
+        unsafe { uiSliderOnChanged(self.as_ptr(), Some(trampoline::<F>), into_data(f)) };
+    }
+}
+
+impl RadioButtons {
+    /// Creates a group of radio buttons with no buttons.
+    ///
+    /// This wraps [`uiNewRadioButtons`].
+    pub fn new() -> Self {
+        new_control!(uiNewRadioButtons())
+    }
+
+    /// Appends a button labeled `text`.
+    ///
+    /// This wraps [`uiRadioButtonsAppend`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `text` contains an interior nul byte.
+    pub fn append(&mut self, text: &str) {
+        check_thread!();
+        let text = CString::new(text).expect("text contains a nul byte");
+        unsafe { uiRadioButtonsAppend(self.as_ptr(), text.as_ptr()) };
+    }
+
+    /// Returns the index of the selected button, or `None` if no button is selected.
+    ///
+    /// This wraps [`uiRadioButtonsSelected`].
+    pub fn selected(&self) -> Option<usize> {
+        check_thread!();
+        usize::try_from(unsafe { uiRadioButtonsSelected(self.as_ptr()) }).ok()
+    }
+
+    /// Selects the button at `index`, or clears the selection if `index` is `None`.
+    ///
+    /// This wraps [`uiRadioButtonsSetSelected`].
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        check_thread!();
+        let index = index.map_or(-1, |index| index as c_int);
+        unsafe { uiRadioButtonsSetSelected(self.as_ptr(), index) };
+    }
+
+    /// Registers a closure to be called with the index of the selected button whenever the user
+    /// changes the selection.
+    ///
+    /// This wraps [`uiRadioButtonsOnSelected`].
+    pub fn on_selected<F: FnMut(Option<usize>) + 'static>(&mut self, f: F) {
+        check_thread!();
+        unsafe extern "C" fn trampoline<F: FnMut(Option<usize>)>(
+            buttons: *mut uiRadioButtons,
+            data: *mut c_void,
+        ) {
+            let selected = usize::try_from(uiRadioButtonsSelected(buttons)).ok();
+            from_data::<F>(data)(selected);
+        }
+
+        unsafe { uiRadioButtonsOnSelected(self.as_ptr(), Some(trampoline::<F>), into_data(f)) };
+    }
+}
+
+impl Default for RadioButtons {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checkbox {
+    /// Creates an unchecked checkbox labeled `text`.
+    ///
+    /// This wraps [`uiNewCheckbox`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `text` contains an interior nul byte.
+    pub fn new(text: &str) -> Self {
+        let text = CString::new(text).expect("text contains a nul byte");
+        new_control!(uiNewCheckbox(text.as_ptr()))
+    }
+
+    /// Determines if this checkbox is checked.
+    ///
+    /// This wraps [`uiCheckboxChecked`].
+    pub fn is_checked(&self) -> bool {
+        check_thread!();
+        unsafe { uiCheckboxChecked(self.as_ptr()) != 0 }
+    }
+
+    /// Checks or unchecks this checkbox.
+    ///
+    /// This wraps [`uiCheckboxSetChecked`].
+    pub fn set_checked(&mut self, checked: bool) {
+        check_thread!();
+        unsafe { uiCheckboxSetChecked(self.as_ptr(), c_int::from(checked)) };
+    }
+
+    /// Registers a closure to be called with the new state whenever the user toggles this
+    /// checkbox.
+    ///
+    /// This wraps [`uiCheckboxOnToggled`].
+    pub fn on_toggled<F: FnMut(bool) + 'static>(&mut self, f: F) {
+        check_thread!();
+        unsafe extern "C" fn trampoline<F: FnMut(bool)>(
+            checkbox: *mut uiCheckbox,
+            data: *mut c_void,
+        ) {
+            from_data::<F>(data)(uiCheckboxChecked(checkbox) != 0);
+        }
+
+        unsafe { uiCheckboxOnToggled(self.as_ptr(), Some(trampoline::<F>), into_data(f)) };
+    }
+}
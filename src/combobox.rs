@@ -0,0 +1,149 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Item and selection management for [`Combobox`] and [`EditableCombobox`].
+
+use std::{
+    ffi::CString,
+    os::raw::{c_int, c_void},
+};
+
+use crate::{
+    callback::{from_data, into_data},
+    control::{new_control, Combobox, EditableCombobox},
+    strings::take_text,
+    *,
+};
+
+impl Combobox {
+    /// Creates a combobox with no items.
+    ///
+    /// This wraps [`uiNewCombobox`].
+    pub fn new() -> Self {
+        new_control!(uiNewCombobox())
+    }
+
+    /// Replaces the items of this combobox with `items`.
+    ///
+    /// This wraps [`uiComboboxClear`] and [`uiComboboxAppend`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if an item contains an interior nul byte.
+    pub fn set_items(&mut self, items: &[&str]) {
+        check_thread!();
+        unsafe { uiComboboxClear(self.as_ptr()) };
+        for item in items {
+            let item = CString::new(*item).expect("item contains a nul byte");
+            unsafe { uiComboboxAppend(self.as_ptr(), item.as_ptr()) };
+        }
+    }
+
+    /// Returns the index of the selected item, or `None` if no item is selected.
+    ///
+    /// This wraps [`uiComboboxSelected`].
+    pub fn selected(&self) -> Option<usize> {
+        check_thread!();
+        usize::try_from(unsafe { uiComboboxSelected(self.as_ptr()) }).ok()
+    }
+
+    /// Selects the item at `index`, or clears the selection if `index` is `None`.
+    ///
+    /// This wraps [`uiComboboxSetSelected`].
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        check_thread!();
+        let index = index.map_or(-1, |index| index as c_int);
+        unsafe { uiComboboxSetSelected(self.as_ptr(), index) };
+    }
+
+    /// Registers a closure to be called with the index of the selected item whenever the user
+    /// changes the selection.
+    ///
+    /// This wraps [`uiComboboxOnSelected`].
+    pub fn on_selected<F: FnMut(Option<usize>) + 'static>(&mut self, f: F) {
+        check_thread!();
+        unsafe extern "C" fn trampoline<F: FnMut(Option<usize>)>(
+            combobox: *mut uiCombobox,
+            data: *mut c_void,
+        ) {
+            let selected = usize::try_from(uiComboboxSelected(combobox)).ok();
+            from_data::<F>(data)(selected);
+        }
+
+        unsafe { uiComboboxOnSelected(self.as_ptr(), Some(trampoline::<F>), into_data(f)) };
+    }
+}
+
+impl Default for Combobox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditableCombobox {
+    /// Creates an editable combobox with no items.
+    ///
+    /// This wraps [`uiNewEditableCombobox`].
+    pub fn new() -> Self {
+        new_control!(uiNewEditableCombobox())
+    }
+
+    /// Appends each of `items` to the suggestions of this combobox.
+    ///
+    /// This wraps [`uiEditableComboboxAppend`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if an item contains an interior nul byte.
+    pub fn append_items(&mut self, items: &[&str]) {
+        check_thread!();
+        for item in items {
+            let item = CString::new(*item).expect("item contains a nul byte");
+            unsafe { uiEditableComboboxAppend(self.as_ptr(), item.as_ptr()) };
+        }
+    }
+
+    /// Returns the text entered in this combobox.
+    ///
+    /// This wraps [`uiEditableComboboxText`].
+    pub fn text(&self) -> String {
+        check_thread!();
+        unsafe { take_text(uiEditableComboboxText(self.as_ptr())) }
+    }
+
+    /// Sets the text entered in this combobox.
+    ///
+    /// This wraps [`uiEditableComboboxSetText`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `text` contains an interior nul byte.
+    pub fn set_text(&mut self, text: &str) {
+        check_thread!();
+        let text = CString::new(text).expect("text contains a nul byte");
+        unsafe { uiEditableComboboxSetText(self.as_ptr(), text.as_ptr()) };
+    }
+
+    /// Registers a closure to be called with the entered text whenever the user changes it.
+    ///
+    /// This wraps [`uiEditableComboboxOnChanged`].
+    pub fn on_changed<F: FnMut(String) + 'static>(&mut self, f: F) {
+        check_thread!();
+        unsafe extern "C" fn trampoline<F: FnMut(String)>(
+            combobox: *mut uiEditableCombobox,
+            data: *mut c_void,
+        ) {
+            let text = take_text(uiEditableComboboxText(combobox));
+            from_data::<F>(data)(text);
+        }
+
+        unsafe { uiEditableComboboxOnChanged(self.as_ptr(), Some(trampoline::<F>), into_data(f)) };
+    }
+}
+
+impl Default for EditableCombobox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
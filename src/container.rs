@@ -0,0 +1,136 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Checked child management and padding for [`Box`] and [`Grid`].
+
+use std::os::raw::c_int;
+
+use crate::{
+    control::{new_control, push_child, remove_child, track_children, Box, Control, Grid},
+    *,
+};
+
+/// The error type returned by [`Box`] functions.
+pub use crate::control::ChildError as Error;
+
+impl Box {
+    /// Creates a box that arranges its children horizontally.
+    ///
+    /// This wraps [`uiNewHorizontalBox`].
+    pub fn horizontal() -> Self {
+        let this: Self = new_control!(uiNewHorizontalBox());
+        track_children(this.0.cast());
+
+        this
+    }
+
+    /// Creates a box that arranges its children vertically.
+    ///
+    /// This wraps [`uiNewVerticalBox`].
+    pub fn vertical() -> Self {
+        let this: Self = new_control!(uiNewVerticalBox());
+        track_children(this.0.cast());
+
+        this
+    }
+
+    /// Appends `child` to this box.
+    ///
+    /// If `stretchy` is `true`, `child` expands to fill any space left over by its siblings. The
+    /// box takes ownership of `child`. This wraps [`uiBoxAppend`].
+    pub fn append(&mut self, child: impl Into<Control>, stretchy: bool) -> Result<(), Error> {
+        check_thread!();
+        let child = child.into();
+        if unsafe { !uiControlParent(child.as_ptr()).is_null() } {
+            return Err(Error::HasParent);
+        }
+
+        unsafe { uiBoxAppend(self.as_ptr(), child.as_ptr(), c_int::from(stretchy)) };
+        push_child(self.0.cast(), child.0);
+
+        Ok(())
+    }
+
+    /// Removes the child at `index`, returning it.
+    ///
+    /// Children after `index` shift down by one. The removed child is *not* destroyed, so
+    /// ownership of it returns to the caller. It can only be returned if every child was appended
+    /// with [`Box::append`] to a box created by [`Box::horizontal`] or [`Box::vertical`];
+    /// otherwise, this returns `Ok(None)`. This wraps [`uiBoxDelete`].
+    pub fn delete(&mut self, index: usize) -> Result<Option<Control>, Error> {
+        check_thread!();
+        let len = self.len();
+        if index >= len {
+            return Err(Error::IndexOutOfBounds { index, len });
+        }
+
+        unsafe { uiBoxDelete(self.as_ptr(), index as c_int) };
+
+        Ok(remove_child(self.0.cast(), index, len))
+    }
+
+    /// Returns the number of children.
+    ///
+    /// This wraps [`uiBoxNumChildren`].
+    pub fn len(&self) -> usize {
+        check_thread!();
+        unsafe { uiBoxNumChildren(self.as_ptr()) as usize }
+    }
+
+    /// Determines if this box has no children.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if this box leaves space between its children.
+    ///
+    /// This wraps [`uiBoxPadded`].
+    pub fn is_padded(&self) -> bool {
+        check_thread!();
+        unsafe { uiBoxPadded(self.as_ptr()) != 0 }
+    }
+
+    /// Sets whether this box leaves space between its children, returning the previous setting.
+    ///
+    /// This wraps [`uiBoxSetPadded`].
+    pub fn set_padded(&mut self, padded: bool) -> bool {
+        let was_padded = self.is_padded();
+        unsafe { uiBoxSetPadded(self.as_ptr(), c_int::from(padded)) };
+
+        was_padded
+    }
+}
+
+impl Grid {
+    /// Creates an empty grid.
+    ///
+    /// This wraps [`uiNewGrid`].
+    pub fn new() -> Self {
+        new_control!(uiNewGrid())
+    }
+
+    /// Determines if this grid leaves space between its cells.
+    ///
+    /// This wraps [`uiGridPadded`].
+    pub fn is_padded(&self) -> bool {
+        check_thread!();
+        unsafe { uiGridPadded(self.as_ptr()) != 0 }
+    }
+
+    /// Sets whether this grid leaves space between its cells, returning the previous setting.
+    ///
+    /// This wraps [`uiGridSetPadded`].
+    pub fn set_padded(&mut self, padded: bool) -> bool {
+        let was_padded = self.is_padded();
+        unsafe { uiGridSetPadded(self.as_ptr(), c_int::from(padded)) };
+
+        was_padded
+    }
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
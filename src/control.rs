@@ -0,0 +1,633 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Typed handles to *libui* controls.
+//!
+//! These are thin wrappers over the raw control pointers; the raw functions remain available for
+//! anything not covered here.
+
+use std::{cell::RefCell, ffi::CString, os::raw::c_int, ptr::NonNull};
+
+use crate::*;
+
+/// The error type returned by functions that manage the children of a container.
+///
+/// This is re-exported as `Error` by the modules that wrap containers.
+#[cfg(any(feature = "container", feature = "tabs"))]
+#[derive(Debug, Eq, PartialEq)]
+pub enum ChildError {
+    /// The control to be appended already has a parent.
+    ///
+    /// Appending it anyway would corrupt *libui*'s tracking of the control's parent.
+    HasParent,
+    /// The child index is not less than the number of children.
+    IndexOutOfBounds { index: usize, len: usize },
+}
+
+#[cfg(any(feature = "container", feature = "tabs"))]
+impl std::fmt::Display for ChildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HasParent => f.write_str("the control already has a parent"),
+            Self::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for {} children", index, len)
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "container", feature = "tabs"))]
+impl std::error::Error for ChildError {}
+
+/// Each container paired with its recorded children.
+type Children = Vec<(NonNull<uiControl>, Vec<NonNull<uiControl>>)>;
+
+thread_local! {
+    /// The children given to each container created by the safe wrappers, in order.
+    ///
+    /// *libui* offers no way to query a container's children, so we record them ourselves.
+    static CHILDREN: RefCell<Children> = const { RefCell::new(Vec::new()) };
+}
+
+/// Starts recording the children of a newly created container.
+///
+/// A destroyed container may have occupied the same address, so any stale record is replaced.
+pub(crate) fn track_children(container: NonNull<uiControl>) {
+    CHILDREN.with(|children| {
+        let mut children = children.borrow_mut();
+        children.retain(|(other, _)| *other != container);
+        children.push((container, Vec::new()));
+    });
+}
+
+/// Records that `child` was appended to `container`.
+#[cfg(any(feature = "container", feature = "tabs"))]
+pub(crate) fn push_child(container: NonNull<uiControl>, child: NonNull<uiControl>) {
+    CHILDREN.with(|children| {
+        if let Some((_, list)) = children.borrow_mut().iter_mut().find(|(it, _)| *it == container) {
+            list.push(child);
+        }
+    });
+}
+
+/// Forgets the child of `container` at `index`, returning it.
+///
+/// `len` is the number of children *libui* reports. Returns `None`, and stops recording the
+/// children of `container`, if they were not all recorded, e.g., because some were appended with
+/// the raw functions.
+#[cfg(any(feature = "container", feature = "tabs"))]
+pub(crate) fn remove_child(
+    container: NonNull<uiControl>,
+    index: usize,
+    len: usize,
+) -> Option<Control> {
+    CHILDREN.with(|children| {
+        let mut children = children.borrow_mut();
+        let position = children.iter().position(|(it, _)| *it == container)?;
+        if children[position].1.len() == len {
+            Some(Control(children[position].1.remove(index)))
+        } else {
+            children.swap_remove(position);
+            None
+        }
+    })
+}
+
+/// Records `child` as the only child of `container`, returning the child it replaces, if any.
+fn replace_child(
+    container: NonNull<uiControl>,
+    child: NonNull<uiControl>,
+) -> Option<NonNull<uiControl>> {
+    CHILDREN.with(|children| {
+        let mut children = children.borrow_mut();
+        let (_, list) = children.iter_mut().find(|(it, _)| *it == container)?;
+
+        std::mem::replace(list, vec![child]).pop()
+    })
+}
+
+/// Forgets the recorded children of `control` and, recursively, of its descendants.
+fn forget_children(control: NonNull<uiControl>) {
+    let list = CHILDREN.with(|children| {
+        let mut children = children.borrow_mut();
+        let position = children.iter().position(|(it, _)| *it == control)?;
+
+        Some(children.swap_remove(position).1)
+    });
+    for child in list.into_iter().flatten() {
+        forget_children(child);
+    }
+}
+
+/// A handle to a control of any kind.
+#[derive(Debug)]
+pub struct Control(pub(crate) NonNull<uiControl>);
+
+impl Control {
+    /// Wraps a raw control pointer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ptr` is null.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a live [`uiControl`].
+    pub unsafe fn from_ptr(ptr: *mut uiControl) -> Self {
+        Self(NonNull::new(ptr).expect("control pointer is null"))
+    }
+
+    /// Returns the raw control pointer.
+    pub fn as_ptr(&self) -> *mut uiControl {
+        self.0.as_ptr()
+    }
+
+    /// Destroys this control along with its children.
+    ///
+    /// Because this consumes the handle, the control cannot be used, or destroyed again,
+    /// afterwards. Typed handles may be destroyed by first converting them into a `Control`. This
+    /// wraps [`uiControlDestroy`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if this control has a parent, which must destroy it instead.
+    pub fn destroy(self) {
+        check_thread!();
+        assert!(
+            unsafe { uiControlParent(self.as_ptr()) }.is_null(),
+            "cannot destroy a control that has a parent",
+        );
+
+        forget_children(self.0);
+        unsafe { uiControlDestroy(self.as_ptr()) };
+    }
+}
+
+/// Functionality common to all controls.
+pub trait AsControl {
+    /// Returns the raw pointer to the underlying [`uiControl`].
+    fn as_control_ptr(&self) -> *mut uiControl;
+
+    /// Determines if this control is enabled.
+    ///
+    /// A control may be enabled but still unusable if one of its ancestors is disabled; see
+    /// [`AsControl::is_enabled_to_user`]. This wraps [`uiControlEnabled`].
+    fn is_enabled(&self) -> bool {
+        check_thread!();
+        unsafe { uiControlEnabled(self.as_control_ptr()) != 0 }
+    }
+
+    /// Determines if this control and all of its ancestors are enabled, i.e., if the user can
+    /// interact with it.
+    ///
+    /// This wraps [`uiControlEnabledToUser`].
+    fn is_enabled_to_user(&self) -> bool {
+        check_thread!();
+        unsafe { uiControlEnabledToUser(self.as_control_ptr()) != 0 }
+    }
+
+    /// Determines if this control is visible.
+    ///
+    /// This wraps [`uiControlVisible`].
+    fn is_visible(&self) -> bool {
+        check_thread!();
+        unsafe { uiControlVisible(self.as_control_ptr()) != 0 }
+    }
+
+    /// Gives this control keyboard focus.
+    ///
+    /// *libui* has no portable focus API, so this dispatches to the native toolkit through
+    /// [`uiControlHandle`]. On macOS, this control must be contained in a window.
+    fn focus(&self) {
+        check_thread!();
+        unsafe { native::focus(uiControlHandle(self.as_control_ptr())) };
+    }
+
+    /// Determines if this control has keyboard focus.
+    ///
+    /// Like [`AsControl::focus`], this dispatches to the native toolkit.
+    fn has_focus(&self) -> bool {
+        check_thread!();
+        unsafe { native::has_focus(uiControlHandle(self.as_control_ptr())) }
+    }
+}
+
+/// Focus handling for each native toolkit, given the handle returned by [`uiControlHandle`].
+mod native {
+    #[cfg(target_os = "linux")]
+    mod ffi {
+        use std::os::raw::{c_int, c_void};
+
+        extern "C" {
+            pub fn gtk_widget_grab_focus(widget: *mut c_void);
+            pub fn gtk_widget_has_focus(widget: *mut c_void) -> c_int;
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub unsafe fn focus(handle: usize) {
+        ffi::gtk_widget_grab_focus(handle as *mut _);
+    }
+
+    #[cfg(target_os = "linux")]
+    pub unsafe fn has_focus(handle: usize) -> bool {
+        ffi::gtk_widget_has_focus(handle as *mut _) != 0
+    }
+
+    #[cfg(target_os = "windows")]
+    mod ffi {
+        use std::os::raw::c_void;
+
+        #[link(name = "user32")]
+        extern "system" {
+            pub fn SetFocus(hwnd: *mut c_void) -> *mut c_void;
+            pub fn GetFocus() -> *mut c_void;
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    pub unsafe fn focus(handle: usize) {
+        ffi::SetFocus(handle as *mut _);
+    }
+
+    #[cfg(target_os = "windows")]
+    pub unsafe fn has_focus(handle: usize) -> bool {
+        ffi::GetFocus() as usize == handle
+    }
+
+    #[cfg(target_os = "macos")]
+    mod ffi {
+        use std::{
+            mem,
+            os::raw::{c_char, c_void},
+        };
+
+        pub type Id = *mut c_void;
+
+        extern "C" {
+            fn sel_registerName(name: *const c_char) -> *mut c_void;
+            fn objc_msgSend();
+        }
+
+        /// Sends the message `sel`, which must be nul-terminated, to `receiver`.
+        pub unsafe fn send(receiver: Id, sel: &[u8]) -> Id {
+            let send: unsafe extern "C" fn(Id, *mut c_void) -> Id =
+                mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+            send(receiver, sel_registerName(sel.as_ptr().cast()))
+        }
+
+        /// Sends the message `sel`, which must be nul-terminated, to `receiver` with a single
+        /// object argument.
+        pub unsafe fn send_with(receiver: Id, sel: &[u8], arg: Id) -> i8 {
+            let send: unsafe extern "C" fn(Id, *mut c_void, Id) -> i8 =
+                mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+            send(receiver, sel_registerName(sel.as_ptr().cast()), arg)
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    pub unsafe fn focus(handle: usize) {
+        let view = handle as ffi::Id;
+        let window = ffi::send(view, b"window\0");
+        if !window.is_null() {
+            ffi::send_with(window, b"makeFirstResponder:\0", view);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    pub unsafe fn has_focus(handle: usize) -> bool {
+        let view = handle as ffi::Id;
+        let window = ffi::send(view, b"window\0");
+
+        !window.is_null() && ffi::send(window, b"firstResponder\0") == view
+    }
+}
+
+impl AsControl for Control {
+    fn as_control_ptr(&self) -> *mut uiControl {
+        self.as_ptr()
+    }
+}
+
+/// Returns the window containing the given control, or the control itself if it is a window.
+///
+/// This walks up the control tree with [`uiControlParent`], identifying windows by
+/// [`uiWindowSignature`]. Returns `None` if the control is not (yet) contained in a window.
+///
+/// # Safety
+///
+/// `control` must point to a live [`uiControl`].
+pub unsafe fn top_level_window(control: *mut uiControl) -> Option<*mut uiWindow> {
+    check_thread!();
+    let mut current = control;
+    while !current.is_null() {
+        if (*current).Signature == uiWindowSignature {
+            return Some(current.cast());
+        }
+        current = uiControlParent(current);
+    }
+
+    None
+}
+
+/// Shows each of the given controls.
+///
+/// This wraps [`uiControlShow`].
+pub fn show_all(controls: &[Control]) {
+    check_thread!();
+    for control in controls {
+        unsafe { uiControlShow(control.as_ptr()) };
+    }
+}
+
+/// Hides each of the given controls.
+///
+/// This wraps [`uiControlHide`].
+pub fn hide_all(controls: &[Control]) {
+    check_thread!();
+    for control in controls {
+        unsafe { uiControlHide(control.as_ptr()) };
+    }
+}
+
+/// Calls a *libui* control constructor and wraps the returned pointer in `Self`.
+///
+/// This panics if the constructor returns null.
+// This is only used by features that extend the typed handles.
+#[allow(unused_macros)]
+macro_rules! new_control {
+    ($ctor:ident($($arg:expr),* $(,)?)) => {{
+        check_thread!();
+        Self(
+            std::ptr::NonNull::new(unsafe { $ctor($($arg),*) })
+                .expect(concat!(stringify!($ctor), "() returned null")),
+        )
+    }};
+}
+
+#[allow(unused_imports)]
+pub(crate) use new_control;
+
+macro_rules! def_control {
+    ($name:ident, $raw:ident $(,)?) => {
+        #[doc = concat!("A handle to a [`", stringify!($raw), "`].")]
+        #[derive(Debug)]
+        pub struct $name(pub(crate) NonNull<$raw>);
+
+        impl $name {
+            #[doc = concat!("Wraps a raw [`", stringify!($raw), "`] pointer.")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if `ptr` is null.
+            ///
+            /// # Safety
+            ///
+            #[doc = concat!("`ptr` must point to a live [`", stringify!($raw), "`].")]
+            pub unsafe fn from_ptr(ptr: *mut $raw) -> Self {
+                Self(NonNull::new(ptr).expect("control pointer is null"))
+            }
+
+            #[doc = concat!("Returns the raw [`", stringify!($raw), "`] pointer.")]
+            pub fn as_ptr(&self) -> *mut $raw {
+                self.0.as_ptr()
+            }
+        }
+
+        impl AsControl for $name {
+            fn as_control_ptr(&self) -> *mut uiControl {
+                self.as_ptr().cast()
+            }
+        }
+
+        impl From<$name> for Control {
+            fn from(control: $name) -> Self {
+                Self(control.0.cast())
+            }
+        }
+    };
+}
+
+def_control!(Box, uiBox);
+def_control!(Checkbox, uiCheckbox);
+def_control!(Combobox, uiCombobox);
+def_control!(EditableCombobox, uiEditableCombobox);
+def_control!(Form, uiForm);
+def_control!(Grid, uiGrid);
+def_control!(Label, uiLabel);
+def_control!(ProgressBar, uiProgressBar);
+def_control!(RadioButtons, uiRadioButtons);
+def_control!(Separator, uiSeparator);
+def_control!(Slider, uiSlider);
+def_control!(Tab, uiTab);
+def_control!(Window, uiWindow);
+
+impl Window {
+    /// Creates a window titled `title` whose content area is `width` by `height` points.
+    ///
+    /// If `has_menubar` is `true`, the window displays the menus, which must have been created
+    /// beforehand. This wraps [`uiNewWindow`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `title` contains an interior nul byte.
+    pub fn new(title: &str, width: i32, height: i32, has_menubar: bool) -> Self {
+        let title = CString::new(title).expect("title contains a nul byte");
+        let window: Self =
+            new_control!(uiNewWindow(title.as_ptr(), width, height, c_int::from(has_menubar)));
+        track_children(window.0.cast());
+
+        window
+    }
+
+    /// Makes `child` the content of this window.
+    ///
+    /// The window takes ownership of `child`, so both are consumed and the window is returned.
+    /// *libui* detaches the previous child rather than destroying it, so if that, too, was given
+    /// to a window created by [`Window::new`] with this method, it is destroyed here instead of
+    /// leaking. This wraps [`uiWindowSetChild`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` already has a parent.
+    pub fn set_child(self, child: impl Into<Control>) -> Self {
+        check_thread!();
+        let child = child.into();
+        assert!(
+            unsafe { uiControlParent(child.as_ptr()) }.is_null(),
+            "cannot make a control that has a parent the child of a window",
+        );
+        unsafe { uiWindowSetChild(self.as_ptr(), child.as_ptr()) };
+
+        if let Some(previous) = replace_child(self.0.cast(), child.0) {
+            Control(previous).destroy();
+        }
+
+        self
+    }
+
+    /// Returns the width and height of the content area of this window, in points.
+    ///
+    /// This wraps [`uiWindowContentSize`].
+    pub fn content_size(&self) -> (i32, i32) {
+        check_thread!();
+        let (mut width, mut height): (c_int, c_int) = (0, 0);
+        unsafe { uiWindowContentSize(self.as_ptr(), &mut width, &mut height) };
+
+        (width, height)
+    }
+
+    /// Resizes the window so that its content area is `width` by `height` points.
+    ///
+    /// This wraps [`uiWindowSetContentSize`].
+    pub fn set_content_size(&mut self, width: i32, height: i32) {
+        check_thread!();
+        unsafe { uiWindowSetContentSize(self.as_ptr(), width, height) };
+    }
+
+    /// Returns the position of the top-left corner of this window on the screen, in points.
+    ///
+    /// Some platforms, such as GTK under Wayland, don't reveal the positions of windows, in which
+    /// case this returns `(0, 0)`. This wraps [`uiWindowPosition`].
+    pub fn position(&self) -> (i32, i32) {
+        check_thread!();
+        let (mut x, mut y): (c_int, c_int) = (0, 0);
+        unsafe { uiWindowPosition(self.as_ptr(), &mut x, &mut y) };
+
+        (x, y)
+    }
+
+    /// Moves the top-left corner of this window to (`x`, `y`) on the screen, in points.
+    ///
+    /// This has no effect on platforms that don't let applications position their windows, such
+    /// as GTK under Wayland. This wraps [`uiWindowSetPosition`].
+    pub fn set_position(&mut self, x: i32, y: i32) {
+        check_thread!();
+        unsafe { uiWindowSetPosition(self.as_ptr(), x, y) };
+    }
+}
+
+impl ProgressBar {
+    /// Creates a progress bar at 0%.
+    ///
+    /// This wraps [`uiNewProgressBar`].
+    pub fn new() -> Self {
+        new_control!(uiNewProgressBar())
+    }
+
+    /// Returns the current percentage, or `None` if the progress bar is indeterminate.
+    ///
+    /// This wraps [`uiProgressBarValue`].
+    pub fn value(&self) -> Option<u32> {
+        check_thread!();
+        u32::try_from(unsafe { uiProgressBarValue(self.as_ptr()) }).ok()
+    }
+
+    /// Sets the current percentage, or makes the progress bar indeterminate if `value` is `None`.
+    ///
+    /// Percentages above 100 are clamped. This wraps [`uiProgressBarSetValue`].
+    pub fn set_value(&mut self, value: Option<u32>) {
+        check_thread!();
+        // *libui* represents the indeterminate state as -1.
+        let value = value.map_or(-1, |value| value.min(100) as c_int);
+        unsafe { uiProgressBarSetValue(self.as_ptr(), value) };
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Label {
+    /// Creates a label displaying `text`.
+    ///
+    /// This wraps [`uiNewLabel`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `text` contains an interior nul byte.
+    pub fn new(text: &str) -> Self {
+        let text = CString::new(text).expect("text contains a nul byte");
+        new_control!(uiNewLabel(text.as_ptr()))
+    }
+}
+
+impl Separator {
+    /// Creates a horizontal line.
+    ///
+    /// This wraps [`uiNewHorizontalSeparator`].
+    pub fn horizontal() -> Self {
+        new_control!(uiNewHorizontalSeparator())
+    }
+
+    /// Creates a vertical line.
+    ///
+    /// This wraps [`uiNewVerticalSeparator`].
+    pub fn vertical() -> Self {
+        new_control!(uiNewVerticalSeparator())
+    }
+}
+
+macro_rules! def_concrete_control {
+    ($($variant:ident($raw:ident, $sig:ident)),* $(,)?) => {
+        /// A raw control pointer tagged with the concrete type of the control.
+        ///
+        /// This is returned by [`downcast`].
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub enum ConcreteControl {
+            $(
+                #[doc = concat!("A [`", stringify!($raw), "`].")]
+                $variant(*mut $raw),
+            )*
+            /// A control whose signature is not one of *libui*'s, such as a custom control.
+            Unknown(*mut uiControl),
+        }
+
+        /// Determines the concrete type of the given control from its signature.
+        ///
+        /// The signatures are those of the `bindings-control-sigs` bindings, e.g.,
+        /// [`uiWindowSignature`].
+        ///
+        /// # Safety
+        ///
+        /// `control` must point to a live [`uiControl`].
+        pub unsafe fn downcast(control: *mut uiControl) -> ConcreteControl {
+            check_thread!();
+            match (*control).Signature {
+                $($sig => ConcreteControl::$variant(control.cast()),)*
+                _ => ConcreteControl::Unknown(control),
+            }
+        }
+    };
+}
+
+def_concrete_control!(
+    Area(uiArea, uiAreaSignature),
+    Box(uiBox, uiBoxSignature),
+    Button(uiButton, uiButtonSignature),
+    Checkbox(uiCheckbox, uiCheckboxSignature),
+    ColorButton(uiColorButton, uiColorButtonSignature),
+    Combobox(uiCombobox, uiComboboxSignature),
+    DateTimePicker(uiDateTimePicker, uiDateTimePickerSignature),
+    EditableCombobox(uiEditableCombobox, uiEditableComboboxSignature),
+    Entry(uiEntry, uiEntrySignature),
+    FontButton(uiFontButton, uiFontButtonSignature),
+    Form(uiForm, uiFormSignature),
+    Grid(uiGrid, uiGridSignature),
+    Group(uiGroup, uiGroupSignature),
+    Label(uiLabel, uiLabelSignature),
+    MultilineEntry(uiMultilineEntry, uiMultilineEntrySignature),
+    ProgressBar(uiProgressBar, uiProgressBarSignature),
+    RadioButtons(uiRadioButtons, uiRadioButtonsSignature),
+    Separator(uiSeparator, uiSeparatorSignature),
+    Slider(uiSlider, uiSliderSignature),
+    Spinbox(uiSpinbox, uiSpinboxSignature),
+    Tab(uiTab, uiTabSignature),
+    Table(uiTable, uiTableSignature),
+    Window(uiWindow, uiWindowSignature),
+);
@@ -0,0 +1,142 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Conversions between the `struct tm` used by date/time pickers and plain calendar fields.
+
+use std::mem::MaybeUninit;
+
+use crate::*;
+
+/// A calendar date and time of day, in the local time zone.
+///
+/// Unlike [`tm`], every field is numbered as one would write it: months run from 1 to 12 and the
+/// year is the full Gregorian year.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DateTime {
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// The day of the month, from 1 to 31.
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    /// Converts a raw [`tm`].
+    ///
+    /// Negative fields, which a valid [`tm`] never contains, are clamped to zero.
+    pub fn from_tm(raw: &tm) -> Self {
+        let field = |it| u32::try_from(it).unwrap_or(0);
+
+        Self {
+            year: raw.tm_year + 1900,
+            month: field(raw.tm_mon) + 1,
+            day: field(raw.tm_mday),
+            hour: field(raw.tm_hour),
+            minute: field(raw.tm_min),
+            second: field(raw.tm_sec),
+        }
+    }
+
+    /// Converts to a raw [`tm`].
+    ///
+    /// The day of the week and of the year are left zeroed, and daylight saving time is marked as
+    /// unknown; *libui* recomputes these itself.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a field does not fit in a C `int`.
+    pub fn to_tm(self) -> tm {
+        let field = |it: u32| i32::try_from(it).expect("field does not fit in a C int");
+
+        // SAFETY: `tm` is plain old data, for which all zeroes is a valid bit pattern.
+        let mut raw: tm = unsafe { MaybeUninit::zeroed().assume_init() };
+        raw.tm_year = self.year.checked_sub(1900).expect("year does not fit in a C int");
+        raw.tm_mon = field(self.month) - 1;
+        raw.tm_mday = field(self.day);
+        raw.tm_hour = field(self.hour);
+        raw.tm_min = field(self.minute);
+        raw.tm_sec = field(self.second);
+        raw.tm_isdst = -1;
+
+        raw
+    }
+}
+
+impl From<(i32, u32, u32, u32, u32, u32)> for DateTime {
+    fn from((year, month, day, hour, minute, second): (i32, u32, u32, u32, u32, u32)) -> Self {
+        Self { year, month, day, hour, minute, second }
+    }
+}
+
+impl From<DateTime> for (i32, u32, u32, u32, u32, u32) {
+    fn from(it: DateTime) -> Self {
+        (it.year, it.month, it.day, it.hour, it.minute, it.second)
+    }
+}
+
+/// Returns the date and time selected by the given picker.
+///
+/// This wraps [`uiDateTimePickerTime`].
+///
+/// # Safety
+///
+/// `picker` must point to a live [`uiDateTimePicker`].
+pub unsafe fn time(picker: *mut uiDateTimePicker) -> DateTime {
+    check_thread!();
+    let mut raw = MaybeUninit::<tm>::zeroed();
+    uiDateTimePickerTime(picker, raw.as_mut_ptr());
+
+    DateTime::from_tm(&raw.assume_init())
+}
+
+/// Selects the given date and time in the given picker.
+///
+/// This wraps [`uiDateTimePickerSetTime`].
+///
+/// # Panics
+///
+/// This function panics under the same conditions as [`DateTime::to_tm`].
+///
+/// # Safety
+///
+/// `picker` must point to a live [`uiDateTimePicker`].
+pub unsafe fn set_time(picker: *mut uiDateTimePicker, time: DateTime) {
+    check_thread!();
+    let raw = time.to_tm();
+    uiDateTimePickerSetTime(picker, &raw);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tm_round_trips() {
+        let time = DateTime { year: 2024, month: 2, day: 29, hour: 23, minute: 59, second: 60 };
+        assert_eq!(DateTime::from_tm(&time.to_tm()), time);
+    }
+
+    #[test]
+    fn to_tm_uses_c_numbering() {
+        let raw = DateTime::from((1999, 12, 31, 8, 30, 15)).to_tm();
+        assert_eq!(raw.tm_year, 99);
+        assert_eq!(raw.tm_mon, 11);
+        assert_eq!(raw.tm_mday, 31);
+        assert_eq!((raw.tm_hour, raw.tm_min, raw.tm_sec), (8, 30, 15));
+        assert_eq!((raw.tm_wday, raw.tm_yday, raw.tm_isdst), (0, 0, -1));
+    }
+
+    #[test]
+    fn from_tm_clamps_negative_fields() {
+        let mut raw = DateTime::from((1970, 1, 1, 0, 0, 0)).to_tm();
+        raw.tm_mday = -3;
+        raw.tm_sec = -1;
+
+        let time = DateTime::from_tm(&raw);
+        assert_eq!((time.day, time.second), (0, 0));
+    }
+}
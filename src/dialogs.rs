@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! File and folder selection dialogs.
+
+use std::{os::raw::c_char, path::PathBuf};
+#[cfg(unix)]
+use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+use crate::{strings::take_bytes, *};
+
+/// Prompts the user to select an existing file.
+///
+/// Returns `None` if the dialog was cancelled. This wraps [`uiOpenFile`].
+///
+/// # Safety
+///
+/// `parent` must point to a live [`uiWindow`].
+pub unsafe fn open_file(parent: *mut uiWindow) -> Option<PathBuf> {
+    check_thread!();
+    take_path(uiOpenFile(parent))
+}
+
+/// Prompts the user to select an existing folder.
+///
+/// Returns `None` if the dialog was cancelled. This wraps [`uiOpenFolder`].
+///
+/// # Safety
+///
+/// `parent` must point to a live [`uiWindow`].
+pub unsafe fn open_folder(parent: *mut uiWindow) -> Option<PathBuf> {
+    check_thread!();
+    take_path(uiOpenFolder(parent))
+}
+
+/// Prompts the user to select a path to save a file to.
+///
+/// Returns `None` if the dialog was cancelled. This wraps [`uiSaveFile`].
+///
+/// # Safety
+///
+/// `parent` must point to a live [`uiWindow`].
+pub unsafe fn save_file(parent: *mut uiWindow) -> Option<PathBuf> {
+    check_thread!();
+    take_path(uiSaveFile(parent))
+}
+
+/// Copies a path returned by a dialog, if any, and then frees it.
+unsafe fn take_path(path: impl Into<*mut c_char>) -> Option<PathBuf> {
+    let path = path.into();
+    if path.is_null() {
+        return None;
+    }
+
+    let bytes = take_bytes(path);
+    // *libui* returns paths as they are encoded by the platform, which on Unix needn't be UTF-8.
+    #[cfg(unix)]
+    let owned = PathBuf::from(OsStr::from_bytes(&bytes));
+    #[cfg(not(unix))]
+    let owned = PathBuf::from(String::from_utf8_lossy(&bytes).into_owned());
+
+    Some(owned)
+}
@@ -0,0 +1,224 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for the drawing API used by area handlers.
+
+use std::mem::MaybeUninit;
+
+use crate::*;
+
+/// A 2D affine transform, wrapping [`uiDrawMatrix`].
+///
+/// Each method applies a transform after those already in the matrix and returns the result, so
+/// transforms may be chained.
+#[derive(Clone, Copy, Debug)]
+pub struct Matrix(uiDrawMatrix);
+
+impl Matrix {
+    /// Returns the identity transform.
+    ///
+    /// This wraps [`uiDrawMatrixSetIdentity`].
+    pub fn identity() -> Self {
+        check_thread!();
+        let mut raw = MaybeUninit::<uiDrawMatrix>::uninit();
+        unsafe {
+            uiDrawMatrixSetIdentity(raw.as_mut_ptr());
+            Self(raw.assume_init())
+        }
+    }
+
+    /// Wraps a raw matrix.
+    pub fn from_raw(raw: uiDrawMatrix) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw matrix.
+    pub fn into_raw(self) -> uiDrawMatrix {
+        self.0
+    }
+
+    /// Returns a pointer to the raw matrix, e.g., for [`uiDrawTransform`].
+    pub fn as_mut_ptr(&mut self) -> *mut uiDrawMatrix {
+        &mut self.0
+    }
+
+    /// Moves by `x` and `y`.
+    ///
+    /// This wraps [`uiDrawMatrixTranslate`].
+    pub fn translate(mut self, x: f64, y: f64) -> Self {
+        check_thread!();
+        unsafe { uiDrawMatrixTranslate(self.as_mut_ptr(), x, y) };
+        self
+    }
+
+    /// Scales by `x` and `y` about the point (`x_center`, `y_center`).
+    ///
+    /// This wraps [`uiDrawMatrixScale`].
+    pub fn scale(mut self, x_center: f64, y_center: f64, x: f64, y: f64) -> Self {
+        check_thread!();
+        unsafe { uiDrawMatrixScale(self.as_mut_ptr(), x_center, y_center, x, y) };
+        self
+    }
+
+    /// Rotates by `amount` radians about the point (`x`, `y`).
+    ///
+    /// This wraps [`uiDrawMatrixRotate`].
+    pub fn rotate(mut self, x: f64, y: f64, amount: f64) -> Self {
+        check_thread!();
+        unsafe { uiDrawMatrixRotate(self.as_mut_ptr(), x, y, amount) };
+        self
+    }
+
+    /// Skews by `x_amount` and `y_amount` radians about the point (`x`, `y`).
+    ///
+    /// This wraps [`uiDrawMatrixSkew`].
+    pub fn skew(mut self, x: f64, y: f64, x_amount: f64, y_amount: f64) -> Self {
+        check_thread!();
+        unsafe { uiDrawMatrixSkew(self.as_mut_ptr(), x, y, x_amount, y_amount) };
+        self
+    }
+
+    /// Applies `other` after this transform.
+    ///
+    /// This wraps [`uiDrawMatrixMultiply`].
+    pub fn multiply(mut self, mut other: Self) -> Self {
+        check_thread!();
+        unsafe { uiDrawMatrixMultiply(self.as_mut_ptr(), other.as_mut_ptr()) };
+        self
+    }
+
+    /// Returns the inverse transform, or `None` if this transform is not invertible.
+    ///
+    /// This wraps [`uiDrawMatrixInvertible`] and [`uiDrawMatrixInvert`].
+    pub fn invert(mut self) -> Option<Self> {
+        check_thread!();
+        unsafe {
+            if uiDrawMatrixInvertible(self.as_mut_ptr()) == 0 {
+                return None;
+            }
+            uiDrawMatrixInvert(self.as_mut_ptr());
+        }
+
+        Some(self)
+    }
+
+    /// Applies this transform to the point (`x`, `y`).
+    ///
+    /// This wraps [`uiDrawMatrixTransformPoint`].
+    pub fn transform_point(mut self, mut x: f64, mut y: f64) -> (f64, f64) {
+        check_thread!();
+        unsafe { uiDrawMatrixTransformPoint(self.as_mut_ptr(), &mut x, &mut y) };
+        (x, y)
+    }
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// A color with red, green, blue, and alpha components, each in the range `0.0..=1.0`, as used
+/// by brushes and color buttons.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Color {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
+impl Color {
+    /// Converts 8-bit components, where 255 corresponds to 1.0.
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let component = |it: u8| f64::from(it) / 255.0;
+
+        Self { r: component(r), g: component(g), b: component(b), a: component(a) }
+    }
+
+    /// Converts to 8-bit components, rounding to the nearest value.
+    ///
+    /// Components outside `0.0..=1.0` are clamped.
+    pub fn to_rgba8(self) -> (u8, u8, u8, u8) {
+        let component = |it: f64| (it.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        (component(self.r), component(self.g), component(self.b), component(self.a))
+    }
+
+    /// Parses a hexadecimal color of the form `RRGGBB` or `RRGGBBAA`, optionally preceded by `#`.
+    ///
+    /// Colors without an alpha component are opaque. Returns `None` if `hex` is not of either
+    /// form.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !matches!(hex.len(), 6 | 8) || !hex.bytes().all(|it| it.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let component = |index: usize| {
+            hex.get(index..index + 2).map_or(Some(u8::MAX), |it| u8::from_str_radix(it, 16).ok())
+        };
+
+        Some(Self::from_rgba8(component(0)?, component(2)?, component(4)?, component(6)?))
+    }
+
+    /// Returns a solid brush of this color.
+    pub fn to_brush(self) -> uiDrawBrush {
+        // SAFETY: `uiDrawBrush` is plain old data, for which all zeroes is a valid bit pattern.
+        let mut brush: uiDrawBrush = unsafe { MaybeUninit::zeroed().assume_init() };
+        brush.Type = uiDrawBrushTypeSolid as _;
+        brush.R = self.r;
+        brush.G = self.g;
+        brush.B = self.b;
+        brush.A = self.a;
+
+        brush
+    }
+}
+
+impl From<(f64, f64, f64, f64)> for Color {
+    fn from((r, g, b, a): (f64, f64, f64, f64)) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl From<Color> for (f64, f64, f64, f64) {
+    fn from(it: Color) -> Self {
+        (it.r, it.g, it.b, it.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgba8_round_trips() {
+        for rgba in [(0, 0, 0, 0), (255, 255, 255, 255), (1, 128, 254, 127)] {
+            let (r, g, b, a) = rgba;
+            assert_eq!(Color::from_rgba8(r, g, b, a).to_rgba8(), rgba);
+        }
+    }
+
+    #[test]
+    fn to_rgba8_clamps() {
+        let color = Color { r: -0.5, g: 1.5, b: 0.5, a: 1.0 };
+        assert_eq!(color.to_rgba8(), (0, 255, 128, 255));
+    }
+
+    #[test]
+    fn from_hex_parses_both_forms() {
+        assert_eq!(Color::from_hex("#ff8000").unwrap().to_rgba8(), (255, 128, 0, 255));
+        assert_eq!(Color::from_hex("FF800040").unwrap().to_rgba8(), (255, 128, 0, 64));
+        assert_eq!(Color::from_hex("#0a0B0c").unwrap().to_rgba8(), (10, 11, 12, 255));
+    }
+
+    #[test]
+    fn from_hex_rejects_invalid_input() {
+        let invalid = ["", "#", "fff", "#ff800", "#ff8000400", "##ff8000", "#gg8000", "#ff 000"];
+        for hex in invalid {
+            assert_eq!(Color::from_hex(hex), None, "{:?}", hex);
+        }
+    }
+}
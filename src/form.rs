@@ -0,0 +1,97 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A builder for forms of labeled controls.
+
+use std::{ffi::CString, os::raw::c_int};
+
+use crate::{control::{new_control, Control, Form}, *};
+
+impl Form {
+    /// Creates an empty form.
+    ///
+    /// This wraps [`uiNewForm`].
+    pub fn new() -> Self {
+        new_control!(uiNewForm())
+    }
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Row {
+    label: CString,
+    control: Control,
+    is_stretchy: bool,
+}
+
+/// Assembles a [`Form`] from labeled rows.
+///
+/// Nothing is created until [`FormBuilder::build`] is called.
+#[derive(Default)]
+pub struct FormBuilder {
+    rows: Vec<Row>,
+    is_padded: bool,
+}
+
+impl FormBuilder {
+    /// Starts a form with no rows.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a row containing `control`, labeled `label`.
+    ///
+    /// If `stretchy` is `true`, the row expands to fill any space left over by the other rows. The
+    /// form takes ownership of `control`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `label` contains an interior nul byte or if `control` already has a parent.
+    pub fn row(mut self, label: &str, control: impl Into<Control>, stretchy: bool) -> Self {
+        check_thread!();
+        let control = control.into();
+        assert!(
+            unsafe { uiControlParent(control.as_ptr()) }.is_null(),
+            "cannot add a control that has a parent to a form",
+        );
+
+        self.rows.push(Row {
+            label: CString::new(label).expect("label contains a nul byte"),
+            control,
+            is_stretchy: stretchy,
+        });
+        self
+    }
+
+    /// Sets whether the form leaves space between its rows.
+    pub fn padded(mut self, padded: bool) -> Self {
+        self.is_padded = padded;
+        self
+    }
+
+    /// Creates the form and appends its rows.
+    ///
+    /// This wraps [`uiFormAppend`] and [`uiFormSetPadded`]. *libui* copies the labels, so they
+    /// are freed on return.
+    pub fn build(self) -> Form {
+        let form = Form::new();
+        for row in self.rows {
+            unsafe {
+                uiFormAppend(
+                    form.as_ptr(),
+                    row.label.as_ptr(),
+                    row.control.as_ptr(),
+                    c_int::from(row.is_stretchy),
+                );
+            }
+        }
+        unsafe { uiFormSetPadded(form.as_ptr(), c_int::from(self.is_padded)) };
+
+        form
+    }
+}
@@ -0,0 +1,96 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Scoped destruction of controls.
+//!
+//! *libui* destroys a control's children along with it and forbids destroying a control that still
+//! has a parent, so destroying controls in the wrong order is a use-after-free. [`DestroyGuard`]
+//! encodes the correct order. [`uiControlDestroy`] remains available for manual use.
+
+use std::{cell::Cell, ptr::NonNull, rc::Rc};
+
+use crate::{control::Control, *};
+
+/// A tracked control pointer, which is nulled once the control is destroyed.
+type Slot = Rc<Cell<Option<NonNull<uiControl>>>>;
+
+/// Destroys a set of tracked controls when dropped.
+///
+/// Only the tracked controls without a parent are passed to [`uiControlDestroy`]; *libui* destroys
+/// their descendants along with them. Every [`TrackedControl`] handle to a destroyed control is
+/// then nulled.
+#[derive(Debug, Default)]
+pub struct DestroyGuard {
+    slots: Vec<Slot>,
+}
+
+/// A handle to a control tracked by a [`DestroyGuard`].
+#[derive(Clone, Debug)]
+pub struct TrackedControl(Slot);
+
+impl TrackedControl {
+    /// Returns the raw control pointer, or `None` if the control was destroyed by its guard.
+    pub fn get(&self) -> Option<*mut uiControl> {
+        self.0.get().map(NonNull::as_ptr)
+    }
+}
+
+impl DestroyGuard {
+    /// Creates a guard that tracks no controls.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking `control`, taking ownership of it.
+    ///
+    /// The control may subsequently be given to a parent, in which case it is destroyed along
+    /// with its top-level ancestor if that, too, is tracked.
+    pub fn track(&mut self, control: impl Into<Control>) -> TrackedControl {
+        let control: Control = control.into();
+        let slot = Rc::new(Cell::new(Some(control.0)));
+        self.slots.push(Rc::clone(&slot));
+
+        TrackedControl(slot)
+    }
+}
+
+impl Drop for DestroyGuard {
+    fn drop(&mut self) {
+        check_thread!();
+        let mut roots = Vec::new();
+        for slot in &self.slots {
+            if let Some(control) = slot.get() {
+                let control = control.as_ptr();
+                if unsafe { uiControlParent(control).is_null() } && !roots.contains(&control) {
+                    roots.push(control);
+                }
+            }
+        }
+
+        // All handles must be nulled before anything is destroyed, as finding a control's root
+        // requires walking its (live) ancestors.
+        for slot in &self.slots {
+            if let Some(control) = slot.get() {
+                if roots.contains(&unsafe { root_of(control.as_ptr()) }) {
+                    slot.set(None);
+                }
+            }
+        }
+
+        for root in roots {
+            unsafe { uiControlDestroy(root) };
+        }
+    }
+}
+
+/// Returns the top-level ancestor of `control`, or `control` itself if it has no parent.
+unsafe fn root_of(mut control: *mut uiControl) -> *mut uiControl {
+    loop {
+        let parent = uiControlParent(control);
+        if parent.is_null() {
+            return control;
+        }
+        control = parent;
+    }
+}
@@ -0,0 +1,96 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Checked initialization of *libui*.
+
+use std::{ffi::CStr, mem};
+#[cfg(any(feature = "runloop", feature = "thread-check"))]
+use std::{sync::OnceLock, thread};
+
+use crate::*;
+
+/// The thread on which [`init`] succeeded or, if *libui* was initialized otherwise, on which a
+/// [`MainLoop`](crate::runloop::MainLoop) was first created.
+#[cfg(any(feature = "runloop", feature = "thread-check"))]
+static UI_THREAD: OnceLock<thread::ThreadId> = OnceLock::new();
+
+/// A [`uiInitOptions`] with its `Size` field filled in.
+///
+/// *libui* doesn't currently check `Size`, which is set to the size of [`uiInitOptions`].
+#[derive(Clone, Copy, Debug)]
+pub struct UiInitOptions(uiInitOptions);
+
+impl UiInitOptions {
+    /// Creates options whose `Size` is the size of [`uiInitOptions`].
+    pub fn new() -> Self {
+        Self(uiInitOptions { Size: mem::size_of::<uiInitOptions>() as _ })
+    }
+
+    /// Returns a pointer suitable for passing to [`uiInit`].
+    pub fn as_mut_ptr(&mut self) -> *mut uiInitOptions {
+        &mut self.0
+    }
+}
+
+impl Default for UiInitOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Initializes *libui*.
+///
+/// This wraps [`uiInit`]. If initialization fails, the error message returned by *libui* is copied
+/// into the returned [`String`] and then freed with [`uiFreeInitError`].
+///
+/// With the `thread-check` feature, the calling thread is recorded so that the safe wrappers
+/// elsewhere in this crate can check, in debug builds, that they are called from it. With the
+/// `runloop` feature, [`MainLoop`](crate::runloop::MainLoop) likewise checks it in all builds.
+pub fn init() -> Result<(), String> {
+    let mut options = UiInitOptions::new();
+    let err = unsafe { uiInit(options.as_mut_ptr()) };
+    if err.is_null() {
+        #[cfg(any(feature = "runloop", feature = "thread-check"))]
+        let _ = UI_THREAD.set(thread::current().id());
+
+        return Ok(());
+    }
+
+    let msg = unsafe { CStr::from_ptr(err) }.to_string_lossy().into_owned();
+    unsafe { uiFreeInitError(err) };
+
+    Err(msg)
+}
+
+/// Panics, in debug builds, if the current thread is not the one on which [`init`] succeeded.
+///
+/// Nothing is checked if *libui* was not initialized through [`init`].
+#[cfg(feature = "thread-check")]
+// This is unused if no other safe wrappers are enabled.
+#[allow(dead_code)]
+#[track_caller]
+pub(crate) fn debug_assert_ui_thread() {
+    if cfg!(debug_assertions) {
+        if let Some(ui_thread) = UI_THREAD.get() {
+            assert_eq!(
+                *ui_thread,
+                thread::current().id(),
+                "libui must only be called from the thread on which it was initialized",
+            );
+        }
+    }
+}
+
+/// Panics if the current thread is not the UI thread, first recording the current thread as such
+/// if *libui* was not initialized through [`init`].
+#[cfg(feature = "runloop")]
+#[track_caller]
+pub(crate) fn assert_ui_thread() {
+    let current = thread::current().id();
+    assert_eq!(
+        *UI_THREAD.get_or_init(|| current),
+        current,
+        "libui must only be called from the thread on which it was initialized",
+    );
+}
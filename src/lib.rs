@@ -12,30 +12,135 @@
     non_upper_case_globals,
 )]
 
+#[cfg(not(feature = "prebuilt-bindings"))]
 macro_rules! include_bindings {
-    ($name:literal) => {
-        include!(concat!(env!("OUT_DIR"), "/", $name, ".rs"));
+    ($suffix:literal) => {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/",
+            env!("LIBUI_NG_SYS_BINDINGS_STEM"),
+            $suffix,
+            ".rs",
+        ));
     };
 }
 
-include_bindings!("bindings");
-include_bindings!("bindings-control-sigs");
+#[cfg(feature = "prebuilt-bindings")]
+macro_rules! include_bindings {
+    ($suffix:literal) => {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/bindings/bindings", $suffix, ".rs"));
+    };
+}
+
+/// Asserts that the current thread is the one on which *libui* was initialized.
+///
+/// This expands to nothing unless the `thread-check` feature is enabled.
+#[allow(unused_macros)]
+macro_rules! check_thread {
+    () => {
+        #[cfg(feature = "thread-check")]
+        $crate::init::debug_assert_ui_thread();
+    };
+}
+
+// With `$LIBUI_NG_SYS_STUB_BINDINGS` set, these are empty, and the build script disables every
+// module below that uses them.
+include_bindings!("");
+include_bindings!("-control-sigs");
+include_bindings!("-info");
+// Unlike the bindings, this always reflects the current build.
+include!(concat!(env!("OUT_DIR"), "/enabled-features.rs"));
+
+#[cfg(all(feature = "area-helper", not(libui_ng_sys_stub_bindings)))]
+pub mod area;
+#[cfg(all(feature = "callbacks", not(libui_ng_sys_stub_bindings)))]
+pub mod callback;
+#[cfg(all(feature = "combobox", not(libui_ng_sys_stub_bindings)))]
+pub mod combobox;
+#[cfg(all(feature = "container", not(libui_ng_sys_stub_bindings)))]
+pub mod container;
+#[cfg(all(feature = "control", not(libui_ng_sys_stub_bindings)))]
+pub mod control;
+#[cfg(all(feature = "datetime", not(libui_ng_sys_stub_bindings)))]
+pub mod datetime;
+#[cfg(all(feature = "dialogs", not(libui_ng_sys_stub_bindings)))]
+pub mod dialogs;
+#[cfg(all(feature = "draw", not(libui_ng_sys_stub_bindings)))]
+pub mod draw;
+#[cfg(all(feature = "form", not(libui_ng_sys_stub_bindings)))]
+pub mod form;
+#[cfg(all(feature = "control", not(libui_ng_sys_stub_bindings)))]
+pub mod guard;
+#[cfg(all(feature = "safe-init", not(libui_ng_sys_stub_bindings)))]
+pub mod init;
+#[cfg(all(feature = "menu", not(libui_ng_sys_stub_bindings)))]
+pub mod menu;
+#[cfg(all(feature = "owned-text", not(libui_ng_sys_stub_bindings)))]
+mod owned_text;
+#[cfg(all(feature = "pickers", not(libui_ng_sys_stub_bindings)))]
+pub mod pickers;
+#[cfg(all(feature = "runloop", not(libui_ng_sys_stub_bindings)))]
+pub mod runloop;
+#[cfg(all(feature = "strings", not(libui_ng_sys_stub_bindings)))]
+pub mod strings;
+#[cfg(all(feature = "tabs", not(libui_ng_sys_stub_bindings)))]
+pub mod tab;
+#[cfg(all(feature = "table-helper", not(libui_ng_sys_stub_bindings)))]
+pub mod table;
+
+// The generated bindings return this in place of strings that must be freed.
+#[cfg(all(feature = "owned-text", not(libui_ng_sys_stub_bindings)))]
+pub use owned_text::UiOwnedText;
 
 /// Platform-specific functionality.
+#[cfg(not(any(feature = "no-platform-bindings", libui_ng_sys_stub_bindings)))]
 pub mod platform {
     macro_rules! def_platform {
-        ($mod:tt, $platform:literal, $header:literal, $os:literal $(,)?) => {
+        (
+            $mod:tt,
+            $platform:literal,
+            $suffix:literal,
+            $os:literal,
+            $handle:literal $(,)?
+        ) => {
             #[doc = concat!("Additional features available on ", $platform, " platforms.")]
             #[cfg(target_os = $os)]
             pub mod $mod {
                 use crate::*;
 
-                include_bindings!($header);
+                include_bindings!($suffix);
+
+                #[doc = concat!(
+                    "Returns the native handle of the given control, which on ", $platform,
+                    " platforms is ", $handle, ".",
+                )]
+                ///
+                /// The handle is returned as an integer that may be cast to the appropriate
+                /// pointer type, e.g., to embed foreign widgets or create rendering surfaces. This
+                /// wraps [`uiControlHandle`].
+                ///
+                /// # Safety
+                ///
+                /// `control` must point to a live [`uiControl`].
+                pub unsafe fn native_handle(control: *mut uiControl) -> usize {
+                    check_thread!();
+                    uiControlHandle(control) as usize
+                }
+
+                // None of the platform headers declare tooltip or accessibility-label
+                // functions, so there is nothing to wrap here yet. Should one appear, its wrapper
+                // belongs in this module, compiled only for the platforms that provide it.
             }
         };
     }
 
-    def_platform!(darwin, "Darwin", "bindings-darwin", "macos");
-    def_platform!(unix, "Unix", "bindings-unix", "linux");
-    def_platform!(windows, "Windows", "bindings-windows", "windows");
+    def_platform!(
+        darwin,
+        "Darwin",
+        "-darwin",
+        "macos",
+        "an `NSView *` (or an `NSWindow *` for windows)",
+    );
+    def_platform!(unix, "Unix", "-unix", "linux", "a `GtkWidget *`");
+    def_platform!(windows, "Windows", "-windows", "windows", "an `HWND`");
 }
@@ -0,0 +1,249 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A builder for menus and handles to their items.
+//!
+//! *libui* requires that all menus be created before the first window. Menus and their items live
+//! for the remainder of the program, so the handles here never free anything.
+
+use std::{
+    ffi::CString,
+    os::raw::{c_int, c_void},
+    ptr::NonNull,
+};
+
+use crate::{
+    callback::{from_data, into_data},
+    control::Window,
+    *,
+};
+
+/// A menu in the menubar.
+#[derive(Debug)]
+pub struct Menu {
+    ptr: NonNull<uiMenu>,
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    /// Returns the raw menu pointer.
+    pub fn as_ptr(&self) -> *mut uiMenu {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns the items of this menu, excluding separators, in the order they were added.
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+}
+
+/// A handle to an item in a [`Menu`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MenuItem(NonNull<uiMenuItem>);
+
+impl MenuItem {
+    /// Returns the raw menu item pointer.
+    pub fn as_ptr(&self) -> *mut uiMenuItem {
+        self.0.as_ptr()
+    }
+
+    /// Allows this item to be clicked.
+    ///
+    /// This wraps [`uiMenuItemEnable`].
+    pub fn enable(&self) {
+        check_thread!();
+        unsafe { uiMenuItemEnable(self.as_ptr()) };
+    }
+
+    /// Prevents this item from being clicked.
+    ///
+    /// This wraps [`uiMenuItemDisable`].
+    pub fn disable(&self) {
+        check_thread!();
+        unsafe { uiMenuItemDisable(self.as_ptr()) };
+    }
+
+    /// Determines if this item is checked.
+    ///
+    /// This wraps [`uiMenuItemChecked`].
+    pub fn is_checked(&self) -> bool {
+        check_thread!();
+        unsafe { uiMenuItemChecked(self.as_ptr()) != 0 }
+    }
+
+    /// Checks or unchecks this item.
+    ///
+    /// This wraps [`uiMenuItemSetChecked`].
+    pub fn set_checked(&self, checked: bool) {
+        check_thread!();
+        unsafe { uiMenuItemSetChecked(self.as_ptr(), c_int::from(checked)) };
+    }
+
+    /// Registers a closure to be called with the active window whenever this item is clicked.
+    ///
+    /// The window is only lent to the closure, which therefore cannot destroy it or give it away.
+    /// This wraps [`uiMenuItemOnClicked`]. *libui* forbids this for the quit item; use
+    /// [`on_should_quit`](crate::callback::on_should_quit) instead.
+    pub fn on_clicked<F: FnMut(&Window) + 'static>(&self, f: F) {
+        check_thread!();
+        unsafe extern "C" fn trampoline<F: FnMut(&Window)>(
+            _: *mut uiMenuItem,
+            window: *mut uiWindow,
+            data: *mut c_void,
+        ) {
+            if let Some(window) = NonNull::new(window) {
+                from_data::<F>(data)(&Window(window));
+            }
+        }
+
+        unsafe { uiMenuItemOnClicked(self.as_ptr(), Some(trampoline::<F>), into_data(f)) };
+    }
+}
+
+enum Entry {
+    Item { name: CString, is_checkable: bool, on_clicked: Option<OnClicked> },
+    Separator,
+    Quit,
+    Preferences(Option<OnClicked>),
+    About(Option<OnClicked>),
+}
+
+type OnClicked = Box<dyn FnMut(&Window)>;
+
+/// Assembles a [`Menu`] from items and separators.
+///
+/// Nothing is created until [`MenuBuilder::build`] is called.
+pub struct MenuBuilder {
+    name: CString,
+    entries: Vec<Entry>,
+}
+
+impl MenuBuilder {
+    /// Starts a menu with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains an interior nul byte.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: CString::new(name).expect("menu name contains a nul byte"),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds an item with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains an interior nul byte.
+    pub fn item(self, name: &str) -> Self {
+        self.push_item(name, false, None)
+    }
+
+    /// Adds an item with the given name that calls `f` when clicked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains an interior nul byte.
+    pub fn item_with<F: FnMut(&Window) + 'static>(self, name: &str, f: F) -> Self {
+        self.push_item(name, false, Some(Box::new(f)))
+    }
+
+    /// Adds an item with the given name that may be checked and unchecked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains an interior nul byte.
+    pub fn check_item(self, name: &str) -> Self {
+        self.push_item(name, true, None)
+    }
+
+    /// Adds a separator.
+    pub fn separator(mut self) -> Self {
+        self.entries.push(Entry::Separator);
+        self
+    }
+
+    /// Adds the platform's standard quit item.
+    ///
+    /// Clicking it consults the handler registered with
+    /// [`on_should_quit`](crate::callback::on_should_quit).
+    pub fn quit_item(mut self) -> Self {
+        self.entries.push(Entry::Quit);
+        self
+    }
+
+    /// Adds the platform's standard preferences item.
+    pub fn preferences_item(mut self) -> Self {
+        self.entries.push(Entry::Preferences(None));
+        self
+    }
+
+    /// Adds the platform's standard preferences item, which calls `f` when clicked.
+    pub fn preferences_item_with<F: FnMut(&Window) + 'static>(mut self, f: F) -> Self {
+        self.entries.push(Entry::Preferences(Some(Box::new(f))));
+        self
+    }
+
+    /// Adds the platform's standard about item.
+    pub fn about_item(mut self) -> Self {
+        self.entries.push(Entry::About(None));
+        self
+    }
+
+    /// Adds the platform's standard about item, which calls `f` when clicked.
+    pub fn about_item_with<F: FnMut(&Window) + 'static>(mut self, f: F) -> Self {
+        self.entries.push(Entry::About(Some(Box::new(f))));
+        self
+    }
+
+    fn push_item(mut self, name: &str, is_checkable: bool, on_clicked: Option<OnClicked>) -> Self {
+        self.entries.push(Entry::Item {
+            name: CString::new(name).expect("item name contains a nul byte"),
+            is_checkable,
+            on_clicked,
+        });
+        self
+    }
+
+    /// Creates the menu and its items.
+    ///
+    /// This wraps [`uiNewMenu`] and the `uiMenuAppend*` functions.
+    pub fn build(self) -> Menu {
+        check_thread!();
+        let ptr = NonNull::new(unsafe { uiNewMenu(self.name.as_ptr()) })
+            .expect("uiNewMenu() returned null");
+
+        let mut items = Vec::new();
+        for entry in self.entries {
+            let (item, on_clicked) = unsafe {
+                match entry {
+                    Entry::Item { name, is_checkable: false, on_clicked } => {
+                        (uiMenuAppendItem(ptr.as_ptr(), name.as_ptr()), on_clicked)
+                    }
+                    Entry::Item { name, is_checkable: true, on_clicked } => {
+                        (uiMenuAppendCheckItem(ptr.as_ptr(), name.as_ptr()), on_clicked)
+                    }
+                    Entry::Separator => {
+                        uiMenuAppendSeparator(ptr.as_ptr());
+                        continue;
+                    }
+                    Entry::Quit => (uiMenuAppendQuitItem(ptr.as_ptr()), None),
+                    Entry::Preferences(on_clicked) => {
+                        (uiMenuAppendPreferencesItem(ptr.as_ptr()), on_clicked)
+                    }
+                    Entry::About(on_clicked) => (uiMenuAppendAboutItem(ptr.as_ptr()), on_clicked),
+                }
+            };
+
+            let item = MenuItem(NonNull::new(item).expect("uiMenuAppend*() returned null"));
+            if let Some(on_clicked) = on_clicked {
+                item.on_clicked(on_clicked);
+            }
+            items.push(item);
+        }
+
+        Menu { ptr, items }
+    }
+}
@@ -0,0 +1,75 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! The type returned by bindings in place of strings that the caller must free.
+
+use std::{borrow::Cow, ffi::CStr, mem, os::raw::c_char};
+
+use crate::*;
+
+/// A string allocated by *libui*, such as that returned by [`uiWindowTitle`].
+///
+/// The string is freed with [`uiFreeText`] when dropped. Some functions, such as [`uiOpenFile`],
+/// return null to indicate the absence of a string, in which case nothing is freed.
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct UiOwnedText(*mut c_char);
+
+impl UiOwnedText {
+    /// Takes ownership of a raw string.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or a string allocated by *libui* that is not used afterwards.
+    pub unsafe fn from_raw(ptr: *mut c_char) -> Self {
+        Self(ptr)
+    }
+
+    /// Returns the raw string, which may be null.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.0
+    }
+
+    /// Consumes this string, returning the raw pointer without freeing it.
+    pub fn into_raw(self) -> *mut c_char {
+        let ptr = self.0;
+        mem::forget(self);
+
+        ptr
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    /// Returns the string, or `None` if it is null.
+    pub fn as_c_str(&self) -> Option<&CStr> {
+        if self.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(self.0) })
+        }
+    }
+
+    /// Returns the string, or `None` if it is null.
+    ///
+    /// Invalid UTF-8 is replaced with U+FFFD REPLACEMENT CHARACTER.
+    pub fn to_string_lossy(&self) -> Option<Cow<'_, str>> {
+        self.as_c_str().map(CStr::to_string_lossy)
+    }
+}
+
+impl Drop for UiOwnedText {
+    fn drop(&mut self) {
+        if !self.is_null() {
+            unsafe { uiFreeText(self.0) };
+        }
+    }
+}
+
+impl From<UiOwnedText> for *mut c_char {
+    fn from(text: UiOwnedText) -> Self {
+        text.into_raw()
+    }
+}
@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Accessors for the values of color and font buttons.
+
+use std::{ffi::CStr, fmt, mem::MaybeUninit};
+
+use crate::*;
+
+/// Returns the color selected by the given color button as `(red, green, blue, alpha)`
+/// components, each in the range `0.0..=1.0`.
+///
+/// This wraps [`uiColorButtonColor`].
+///
+/// # Safety
+///
+/// `button` must point to a live [`uiColorButton`].
+pub unsafe fn color(button: *mut uiColorButton) -> (f64, f64, f64, f64) {
+    check_thread!();
+    let (mut r, mut g, mut b, mut a) = (0.0, 0.0, 0.0, 0.0);
+    uiColorButtonColor(button, &mut r, &mut g, &mut b, &mut a);
+
+    (r, g, b, a)
+}
+
+/// A font selected by a font button.
+///
+/// This owns a [`uiFontDescriptor`] filled in by *libui* and frees its family name with
+/// [`uiFreeFontButtonFont`] on drop.
+pub struct FontDescriptor(uiFontDescriptor);
+
+impl FontDescriptor {
+    /// Returns the font family name.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the family name is not valid UTF-8.
+    pub fn family(&self) -> &str {
+        unsafe { CStr::from_ptr(self.0.Family) }
+            .to_str()
+            .expect("font family is not valid UTF-8")
+    }
+
+    /// Returns the font size in points.
+    pub fn size(&self) -> f64 {
+        self.0.Size
+    }
+
+    /// Returns the font weight, e.g., [`uiTextWeightBold`].
+    pub fn weight(&self) -> uiTextWeight {
+        self.0.Weight
+    }
+
+    /// Returns whether the font is upright, oblique, or italic, e.g., [`uiTextItalicItalic`].
+    pub fn italic(&self) -> uiTextItalic {
+        self.0.Italic
+    }
+
+    /// Returns the font width, e.g., [`uiTextStretchCondensed`].
+    pub fn stretch(&self) -> uiTextStretch {
+        self.0.Stretch
+    }
+
+    /// Returns a pointer to the underlying [`uiFontDescriptor`], which remains valid for as long
+    /// as this descriptor.
+    pub fn as_ptr(&self) -> *const uiFontDescriptor {
+        &self.0
+    }
+}
+
+impl fmt::Debug for FontDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Unlike `family`, this must not panic on a family name that isn't valid UTF-8.
+        let family = unsafe { CStr::from_ptr(self.0.Family) }.to_string_lossy();
+        f.debug_struct("FontDescriptor")
+            .field("family", &family)
+            .field("size", &self.size())
+            .field("weight", &self.weight())
+            .field("italic", &self.italic())
+            .field("stretch", &self.stretch())
+            .finish()
+    }
+}
+
+impl Drop for FontDescriptor {
+    fn drop(&mut self) {
+        unsafe { uiFreeFontButtonFont(&mut self.0) };
+    }
+}
+
+/// Returns the font selected by the given font button.
+///
+/// This wraps [`uiFontButtonFont`].
+///
+/// # Safety
+///
+/// `button` must point to a live [`uiFontButton`].
+pub unsafe fn font(button: *mut uiFontButton) -> FontDescriptor {
+    check_thread!();
+    let mut raw = MaybeUninit::<uiFontDescriptor>::zeroed();
+    uiFontButtonFont(button, raw.as_mut_ptr());
+
+    FontDescriptor(raw.assume_init())
+}
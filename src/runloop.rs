@@ -0,0 +1,163 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A structured interface to the *libui* main loop.
+
+use std::{
+    cell::Cell,
+    marker::PhantomData,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::*;
+
+/// The state of the main loop, which *libui* doesn't expose.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum State {
+    /// The main loop hasn't started or has already stopped.
+    Idle,
+    /// The main loop is running or being stepped.
+    Running,
+    /// [`uiQuit`] has been called, but the main loop hasn't stopped yet.
+    Quitting,
+}
+
+thread_local! {
+    static STATE: Cell<State> = const { Cell::new(State::Idle) };
+}
+
+/// Requests that the main loop stop, if it is running.
+///
+/// *libui* must not be asked to quit before its main loop starts or more than once, so this does
+/// nothing and returns `false` unless the main loop was started with [`MainLoop::run`] or
+/// [`MainLoop::step`] and hasn't already been asked to quit. Otherwise, this returns `true`. Unlike
+/// [`MainLoop::quit`], this may be called where no `MainLoop` is at hand, e.g., from a
+/// [`uiWindowOnClosing`] handler.
+///
+/// Calls to the raw [`uiQuit`] aren't tracked, nor are those *libui* makes itself, e.g., when a
+/// [`uiOnShouldQuit`] handler returns nonzero. This wraps [`uiQuit`].
+pub fn quit() -> bool {
+    check_thread!();
+    STATE.with(|state| {
+        if state.get() != State::Running {
+            return false;
+        }
+
+        unsafe { uiQuit() };
+        state.set(State::Quitting);
+        true
+    })
+}
+
+/// The longest [`MainLoop::step`] sleeps between polls when given a timeout.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The outcome of [`MainLoop::step`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StepResult {
+    /// An event was waited for and processed.
+    Processed,
+    /// The timeout elapsed. Any pending events were processed, though *libui* doesn't report
+    /// whether there were any.
+    Polled,
+    /// [`MainLoop::quit`] or [`quit`] was called, so the main loop should not be stepped again.
+    Quit,
+}
+
+/// A handle to the *libui* main loop.
+///
+/// This type wraps [`uiMain`], [`uiMainSteps`], [`uiMainStep`], and [`uiQuit`]. The raw functions
+/// remain available for use alongside it.
+///
+/// `MainLoop` is neither [`Send`] nor [`Sync`]; it is bound to the thread on which it was created.
+pub struct MainLoop {
+    /// Whether [`uiMainSteps`] has been called.
+    is_stepping: bool,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl MainLoop {
+    /// Creates a handle to the main loop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if *libui* was initialized with [`init::init`] on a different thread or, failing
+    /// that, if a `MainLoop` was previously created on a different thread.
+    ///
+    /// # Safety
+    ///
+    /// *libui* must have been initialized with [`uiInit`] on the current thread.
+    pub unsafe fn new() -> Self {
+        init::assert_ui_thread();
+
+        Self {
+            is_stepping: false,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Runs the main loop until [`MainLoop::quit`] is called.
+    ///
+    /// This wraps [`uiMain`].
+    pub fn run(&self) {
+        check_thread!();
+        STATE.with(|state| state.set(State::Running));
+        unsafe { uiMain() };
+        STATE.with(|state| state.set(State::Idle));
+    }
+
+    /// Processes pending events, for integrating *libui* with an external event loop.
+    ///
+    /// If `timeout` is `None`, this blocks until an event is available and processes it. Otherwise,
+    /// this processes any pending events without blocking and then, until `timeout` elapses or
+    /// [`MainLoop::quit`] is called, keeps polling for more. *libui* cannot wait for an event with
+    /// a timeout, so polls are spaced by up to [`POLL_INTERVAL`]; pass [`Duration::ZERO`] to poll
+    /// exactly once.
+    ///
+    /// This wraps [`uiMainSteps`] and [`uiMainStep`]. The `wait` argument of the latter is
+    /// nonzero only when `timeout` is `None`.
+    pub fn step(&mut self, timeout: Option<Duration>) -> StepResult {
+        check_thread!();
+        if !self.is_stepping {
+            // *libui* requires that `uiMainSteps` precede the first call to `uiMainStep`.
+            unsafe { uiMainSteps() };
+            self.is_stepping = true;
+            STATE.with(|state| state.set(State::Running));
+        }
+
+        let timeout = match timeout {
+            Some(it) => it,
+            None => {
+                return if unsafe { uiMainStep(1) } == 0 {
+                    STATE.with(|state| state.set(State::Idle));
+                    StepResult::Quit
+                } else {
+                    StepResult::Processed
+                };
+            }
+        };
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            if unsafe { uiMainStep(0) } == 0 {
+                STATE.with(|state| state.set(State::Idle));
+                return StepResult::Quit;
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return StepResult::Polled;
+            }
+            thread::sleep(remaining.min(POLL_INTERVAL));
+        }
+    }
+
+    /// Requests that the main loop stop, if it is running.
+    ///
+    /// This is equivalent to [`quit`].
+    pub fn quit(&self) -> bool {
+        quit()
+    }
+}
@@ -0,0 +1,54 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Conversions between Rust strings and the C strings passed to and from *libui*.
+
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+};
+
+use crate::*;
+
+/// Calls `f` with a pointer to a nul-terminated copy of `s`.
+///
+/// The copy lives until `f` returns, which suffices for setters such as [`uiWindowSetTitle`] and
+/// [`uiLabelSetText`], as *libui* copies the strings it is given.
+///
+/// # Panics
+///
+/// Panics if `s` contains an interior nul byte.
+pub fn with_cstr<R>(s: &str, f: impl FnOnce(*const c_char) -> R) -> R {
+    let s = CString::new(s).expect("string contains a nul byte");
+
+    f(s.as_ptr())
+}
+
+/// Copies a string returned by *libui*, such as that of [`uiLabelText`], and then frees it.
+///
+/// Invalid UTF-8 is replaced with U+FFFD REPLACEMENT CHARACTER. This wraps [`uiFreeText`].
+///
+/// `text` may also be a `UiOwnedText` if the `owned-text` feature is enabled.
+///
+/// # Safety
+///
+/// `text` must be a non-null string allocated by *libui* that is not used afterwards.
+pub unsafe fn take_text(text: impl Into<*mut c_char>) -> String {
+    String::from_utf8_lossy(&take_bytes(text)).into_owned()
+}
+
+/// Like [`take_text`], but copies the string as bytes without validating it as UTF-8.
+///
+/// This suits strings in platform encodings, such as the paths returned by [`uiOpenFile`].
+///
+/// # Safety
+///
+/// `text` must be a non-null string allocated by *libui* that is not used afterwards.
+pub unsafe fn take_bytes(text: impl Into<*mut c_char>) -> Vec<u8> {
+    let text = text.into();
+    let owned = CStr::from_ptr(text).to_bytes().to_vec();
+    uiFreeText(text);
+
+    owned
+}
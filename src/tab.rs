@@ -0,0 +1,93 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Bounds-checked page management for [`Tab`].
+
+use std::{ffi::CString, os::raw::c_int};
+
+use crate::{
+    control::{new_control, push_child, remove_child, track_children, Control, Tab},
+    *,
+};
+
+/// The error type returned by [`Tab`] functions.
+///
+/// A page index that is out of bounds is reported as [`Error::IndexOutOfBounds`].
+pub use crate::control::ChildError as Error;
+
+impl Tab {
+    /// Creates a tab with no pages.
+    ///
+    /// This wraps [`uiNewTab`].
+    pub fn new() -> Self {
+        let this: Self = new_control!(uiNewTab());
+        track_children(this.0.cast());
+
+        this
+    }
+
+    /// Appends a page named `name` whose content is `child`.
+    ///
+    /// The tab takes ownership of `child`. This wraps [`uiTabAppend`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains an interior nul byte.
+    pub fn append(&mut self, name: &str, child: impl Into<Control>) -> Result<(), Error> {
+        check_thread!();
+        let child = child.into();
+        if unsafe { !uiControlParent(child.as_ptr()).is_null() } {
+            return Err(Error::HasParent);
+        }
+
+        let name = CString::new(name).expect("page name contains a nul byte");
+        unsafe { uiTabAppend(self.as_ptr(), name.as_ptr(), child.as_ptr()) };
+        push_child(self.0.cast(), child.0);
+
+        Ok(())
+    }
+
+    /// Removes the page at `index`, returning its content.
+    ///
+    /// Pages after `index` shift down by one. The content of the removed page is *not* destroyed,
+    /// so ownership of it returns to the caller. It can only be returned if every page was
+    /// appended with [`Tab::append`] to a tab created by [`Tab::new`]; otherwise, this returns
+    /// `Ok(None)`. This wraps [`uiTabDelete`].
+    pub fn delete(&mut self, index: usize) -> Result<Option<Control>, Error> {
+        check_thread!();
+        let len = self.len();
+        let raw_index = self.check_index(index)?;
+        unsafe { uiTabDelete(self.as_ptr(), raw_index) };
+
+        Ok(remove_child(self.0.cast(), index, len))
+    }
+
+    /// Returns the number of pages.
+    ///
+    /// This wraps [`uiTabNumPages`].
+    pub fn len(&self) -> usize {
+        check_thread!();
+        unsafe { uiTabNumPages(self.as_ptr()) as usize }
+    }
+
+    /// Determines if this tab has no pages.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check_index(&self, index: usize) -> Result<c_int, Error> {
+        let len = self.len();
+        if index < len {
+            Ok(index as c_int)
+        } else {
+            Err(Error::IndexOutOfBounds { index, len })
+        }
+    }
+}
+
+impl Default for Tab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,201 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for populating tables.
+
+use std::{marker::PhantomData, mem, os::raw::c_int, ptr::NonNull};
+
+use crate::*;
+
+/// An owned [`uiImage`].
+///
+/// The image is freed with [`uiFreeImage`] when dropped.
+#[derive(Debug)]
+pub struct Image(NonNull<uiImage>);
+
+impl Image {
+    /// Creates an image from a buffer of 8-bit RGBA pixels in row-major order.
+    ///
+    /// The image is displayed at a size of `width` by `height` points. This wraps [`uiNewImage`]
+    /// and [`uiImageAppend`]; *libui* copies the pixels, so `pixels` need not outlive the image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` does not contain exactly `width * height` pixels.
+    pub fn from_rgba(width: u32, height: u32, pixels: &[u8]) -> Self {
+        check_thread!();
+        assert_eq!(
+            pixels.len(),
+            (width as usize) * (height as usize) * 4,
+            "pixel buffer does not match the image dimensions",
+        );
+
+        let image = unsafe { uiNewImage(f64::from(width), f64::from(height)) };
+        let image = Self(NonNull::new(image).expect("uiNewImage() returned null"));
+        unsafe {
+            uiImageAppend(
+                image.as_ptr(),
+                pixels.as_ptr() as *mut _,
+                width as c_int,
+                height as c_int,
+                (width * 4) as c_int,
+            );
+        }
+
+        image
+    }
+
+    /// Returns the raw image pointer.
+    pub fn as_ptr(&self) -> *mut uiImage {
+        self.0.as_ptr()
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        unsafe { uiFreeImage(self.as_ptr()) };
+    }
+}
+
+/// An owned [`uiTableValue`].
+///
+/// The value is freed with [`uiFreeTableValue`] when dropped unless ownership is transferred to
+/// *libui* with [`TableValue::into_raw`].
+#[derive(Debug)]
+pub struct TableValue<'a> {
+    ptr: NonNull<uiTableValue>,
+    _borrows: PhantomData<&'a Image>,
+}
+
+impl<'a> TableValue<'a> {
+    /// Creates a table value that displays the given image.
+    ///
+    /// *libui* does not copy the image, so it must outlive the value. This wraps
+    /// [`uiNewTableValueImage`].
+    pub fn image(image: &'a Image) -> Self {
+        check_thread!();
+        let ptr = unsafe { uiNewTableValueImage(image.as_ptr()) };
+
+        Self {
+            ptr: NonNull::new(ptr).expect("uiNewTableValueImage() returned null"),
+            _borrows: PhantomData,
+        }
+    }
+
+    /// Returns the raw table value pointer.
+    pub fn as_ptr(&self) -> *mut uiTableValue {
+        self.ptr.as_ptr()
+    }
+
+    /// Consumes this value, returning the raw pointer without freeing it.
+    ///
+    /// This is suitable for returning the value from a [`uiTableModelHandler`]'s `CellValue`
+    /// callback, after which *libui* owns the value.
+    pub fn into_raw(self) -> *mut uiTableValue {
+        let ptr = self.as_ptr();
+        mem::forget(self);
+
+        ptr
+    }
+}
+
+impl Drop for TableValue<'_> {
+    fn drop(&mut self) {
+        unsafe { uiFreeTableValue(self.as_ptr()) };
+    }
+}
+
+/// The type of the values in a column of a table model, corresponding to [`uiTableValueType`].
+///
+/// This is what a [`uiTableModelHandler`]'s `ColumnType` callback reports. Checkbox and progress
+/// bar columns hold [`ColumnType::Int`] values, as do button columns that are conditionally
+/// clickable.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ColumnType {
+    Text,
+    Image,
+    Int,
+    Color,
+}
+
+impl ColumnType {
+    /// Converts a raw [`uiTableValueType`], returning `None` if it is unrecognized.
+    pub fn from_raw(raw: uiTableValueType) -> Option<Self> {
+        let kind = match raw {
+            uiTableValueTypeString => Self::Text,
+            uiTableValueTypeImage => Self::Image,
+            uiTableValueTypeInt => Self::Int,
+            uiTableValueTypeColor => Self::Color,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+
+    /// Converts to a raw [`uiTableValueType`].
+    pub fn to_raw(self) -> uiTableValueType {
+        match self {
+            Self::Text => uiTableValueTypeString,
+            Self::Image => uiTableValueTypeImage,
+            Self::Int => uiTableValueTypeInt,
+            Self::Color => uiTableValueTypeColor,
+        }
+    }
+}
+
+/// Returns the indices of the selected rows of the given table.
+///
+/// This wraps [`uiTableGetSelection`], freeing the returned [`uiTableSelection`] with
+/// [`uiFreeTableSelection`].
+///
+/// # Safety
+///
+/// `table` must point to a live [`uiTable`].
+pub unsafe fn table_selection(table: *mut uiTable) -> Vec<usize> {
+    check_thread!();
+    let selection = uiTableGetSelection(table);
+    if selection.is_null() {
+        return Vec::new();
+    }
+
+    let rows = match usize::try_from((*selection).NumRows) {
+        Ok(len) if len > 0 && !(*selection).Rows.is_null() => {
+            std::slice::from_raw_parts((*selection).Rows, len)
+                .iter()
+                .filter_map(|&row| usize::try_from(row).ok())
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    uiFreeTableSelection(selection);
+
+    rows
+}
+
+/// Selects exactly the rows of the given table at `rows`.
+///
+/// An empty slice clears the selection. This wraps [`uiTableSetSelection`]; *libui* does not take
+/// ownership of the selection, which is freed on return.
+///
+/// # Panics
+///
+/// Panics if a row index, or the number of rows, does not fit in a C `int`.
+///
+/// # Safety
+///
+/// `table` must point to a live [`uiTable`], and every index in `rows` must be less than the
+/// number of rows in its model.
+pub unsafe fn set_table_selection(table: *mut uiTable, rows: &[usize]) {
+    check_thread!();
+    let mut rows = rows
+        .iter()
+        .map(|&row| c_int::try_from(row).expect("row index does not fit in a C int"))
+        .collect::<Vec<_>>();
+    let mut selection = uiTableSelection {
+        NumRows: c_int::try_from(rows.len()).expect("too many rows to select"),
+        Rows: rows.as_mut_ptr(),
+    };
+
+    uiTableSetSelection(table, &mut selection);
+}
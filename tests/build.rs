@@ -0,0 +1,17 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests for the helpers used by the build script.
+//!
+//! Cargo never builds a build script with `cfg(test)`, so the helpers are included here instead,
+//! along with the tests that sit next to them.
+
+#[path = "../build/depfile.rs"]
+mod depfile;
+// The build script also emits what it links, which the tests don't.
+#[allow(dead_code)]
+#[path = "../build/link.rs"]
+mod link;
+#[path = "../build/version.rs"]
+mod version;